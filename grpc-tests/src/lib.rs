@@ -0,0 +1,95 @@
+//! Status made of Blunder goes through a real tonic server and client.
+#![cfg(test)]
+
+use std::convert::Infallible;
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use blunder::{Blunder, BsdError};
+use tokio::net::TcpListener;
+use tonic::body::Body;
+use tonic::codegen::http::{self, uri::PathAndQuery};
+use tonic::codegen::tokio_stream::wrappers::TcpListenerStream;
+use tonic::codegen::Service;
+use tonic::server::{Grpc, NamedService};
+use tonic::transport::{Endpoint, Server};
+use tonic::{Code, Request, Response, Status};
+use tonic_prost::ProstCodec;
+use tonic_types::StatusExt;
+
+/// Unary handler that never finds the user.
+struct GetUser;
+
+impl Service<Request<()>> for GetUser {
+    type Response = Response<()>;
+    type Error = Status;
+    type Future = Ready<Result<Response<()>, Status>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Status>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _request: Request<()>) -> Self::Future {
+        ready(Err(Blunder::with_detail(BsdError::ENOENT, "no such user").into()))
+    }
+}
+
+/// blunder.Users service with the only method, what tonic-build would
+/// generate for it.
+#[derive(Clone)]
+struct Users;
+
+impl NamedService for Users {
+    const NAME: &'static str = "blunder.Users";
+}
+
+impl Service<http::Request<Body>> for Users {
+    type Response = http::Response<Body>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<http::Response<Body>, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<Body>) -> Self::Future {
+        Box::pin(async move {
+            let mut grpc = Grpc::new(ProstCodec::<(), ()>::default());
+            Ok(grpc.unary(GetUser, request).await)
+        })
+    }
+}
+
+#[tokio::test]
+async fn status_over_the_wire() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        Server::builder()
+            .add_service(Users)
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+
+    let channel = Endpoint::from_shared(format!("http://{}", addr))
+        .unwrap()
+        .connect()
+        .await
+        .unwrap();
+    let mut client = tonic::client::Grpc::new(channel);
+    client.ready().await.unwrap();
+    let status = client
+        .unary(
+            Request::new(()),
+            PathAndQuery::from_static("/blunder.Users/GetUser"),
+            ProstCodec::<(), ()>::default(),
+        )
+        .await
+        .unwrap_err();
+
+    assert_eq!(status.code(), Code::NotFound);
+    assert_eq!(status.message(), "no such user");
+    let info = status.get_details_error_info().unwrap();
+    assert_eq!(info.reason, "ENOENT");
+    assert_eq!(info.domain, "errno");
+}
@@ -1,38 +1,113 @@
-
-use errno::errno;
+#[cfg(test)]
 use num::FromPrimitive;
-use std::error::Error;
-use std::fmt::{Display, Formatter, Result};
 
-enum_from_primitive! {
-    #[derive(Debug, PartialEq, Clone)]
+errno_enum! {
     /// Errors that can be encoutered while working with FreeBSD's libc.
     /// Some of them are BSD specific, some of them are from POSIX.
     pub enum BsdError {
-        EPERM=      1,  ENOENT, ESRCH, EINTR, EIO, ENXIO, E2BIG, ENOEXEC, EBADF,
-        ECHILD=     10, EDEADLK, ENOMEM, EACCES, EFAULT, ENOTBLK, EBUSY, EEXIST, EXDEV, ENODEV,
-        ENOTDIR=    20, EISDIR, EINVAL, ENFILE, EMFILE, ENOTTY, ETXTBSY, EFBIG, ENOSPC, ESPIPE,
-        EROFS=      30, EMLINK, EPIPE, EDOM, ERANGE, EAGAIN, EINPROGRESS, EALREADY, ENOTSOCK, EDESTADDRREQ,
-        EMSGSIZE=   40, EPROTOTYPE, ENOPROTOOPT, EPROTONOSUPPORT, ESOCKTNOSUPPORT, EOPNOTSUPP, EPFNOSUPPORT, EAFNOSUPPORT, EADDRINUSE, EADDRNOTAVAIL,
-        ENETDOWN=   50, ENETUNREACH, ENETRESET, ECONNABORTED, ECONNRESET, ENOBUFS, EISCONN, ENOTCONN, ESHUTDOWN,
-        ETIMEDOUT=  60, ECONNREFUSED, ELOOP, ENAMETOOLONG, EHOSTDOWN, EHOSTUNREACH, ENOTEMPTY, EPROCLIM, EUSERS, EDQUOT,
-        ESTALE=     70, EBADRPC, ERPCMISMATCH, EPROGUNAVAIL, EPROGMISMATCH, EPROCUNAVAIL, ENOLCK, ENOSYS, EFTYPE,
-        EAUTH=      80, ENEEDAUTH, EIDRM, ENOMSG, EOVERFLOW, ECANCELED, EILSEQ, ENOATTR, EDOOFUS, EBADMSG,
-        EMULTIHOP=  90, ENOLINK, EPROTO, ENOTCAPABLE, ECAPMODE, ENOTRECOVERABLE, EOWNERDEAD
-
+        EPERM = 1 => "Operation not permitted",
+        ENOENT = 2 => "No such file or directory",
+        ESRCH = 3 => "No such process",
+        EINTR = 4 => "Interrupted system call",
+        EIO = 5 => "Input/output error",
+        ENXIO = 6 => "Device not configured",
+        E2BIG = 7 => "Argument list too long",
+        ENOEXEC = 8 => "Exec format error",
+        EBADF = 9 => "Bad file descriptor",
+        ECHILD = 10 => "No child processes",
+        EDEADLK = 11 => "Resource deadlock avoided",
+        ENOMEM = 12 => "Cannot allocate memory",
+        EACCES = 13 => "Permission denied",
+        EFAULT = 14 => "Bad address",
+        ENOTBLK = 15 => "Block device required",
+        EBUSY = 16 => "Device busy",
+        EEXIST = 17 => "File exists",
+        EXDEV = 18 => "Cross-device link",
+        ENODEV = 19 => "Operation not supported by device",
+        ENOTDIR = 20 => "Not a directory",
+        EISDIR = 21 => "Is a directory",
+        EINVAL = 22 => "Invalid argument",
+        ENFILE = 23 => "Too many open files in system",
+        EMFILE = 24 => "Too many open files",
+        ENOTTY = 25 => "Inappropriate ioctl for device",
+        ETXTBSY = 26 => "Text file busy",
+        EFBIG = 27 => "File too large",
+        ENOSPC = 28 => "No space left on device",
+        ESPIPE = 29 => "Illegal seek",
+        EROFS = 30 => "Read-only file system",
+        EMLINK = 31 => "Too many links",
+        EPIPE = 32 => "Broken pipe",
+        EDOM = 33 => "Numerical argument out of domain",
+        ERANGE = 34 => "Result too large",
+        EAGAIN = 35 => "Resource temporarily unavailable",
+        EINPROGRESS = 36 => "Operation now in progress",
+        EALREADY = 37 => "Operation already in progress",
+        ENOTSOCK = 38 => "Socket operation on non-socket",
+        EDESTADDRREQ = 39 => "Destination address required",
+        EMSGSIZE = 40 => "Message too long",
+        EPROTOTYPE = 41 => "Protocol wrong type for socket",
+        ENOPROTOOPT = 42 => "Protocol not available",
+        EPROTONOSUPPORT = 43 => "Protocol not supported",
+        ESOCKTNOSUPPORT = 44 => "Socket type not supported",
+        EOPNOTSUPP = 45 => "Operation not supported",
+        EPFNOSUPPORT = 46 => "Protocol family not supported",
+        EAFNOSUPPORT = 47 => "Address family not supported by protocol family",
+        EADDRINUSE = 48 => "Address already in use",
+        EADDRNOTAVAIL = 49 => "Can't assign requested address",
+        ENETDOWN = 50 => "Network is down",
+        ENETUNREACH = 51 => "Network is unreachable",
+        ENETRESET = 52 => "Network dropped connection on reset",
+        ECONNABORTED = 53 => "Software caused connection abort",
+        ECONNRESET = 54 => "Connection reset by peer",
+        ENOBUFS = 55 => "No buffer space available",
+        EISCONN = 56 => "Socket is already connected",
+        ENOTCONN = 57 => "Socket is not connected",
+        ESHUTDOWN = 58 => "Can't send after socket shutdown",
+        ETOOMANYREFS = 59 => "Too many references: can't splice",
+        ETIMEDOUT = 60 => "Operation timed out",
+        ECONNREFUSED = 61 => "Connection refused",
+        ELOOP = 62 => "Too many levels of symbolic links",
+        ENAMETOOLONG = 63 => "File name too long",
+        EHOSTDOWN = 64 => "Host is down",
+        EHOSTUNREACH = 65 => "No route to host",
+        ENOTEMPTY = 66 => "Directory not empty",
+        EPROCLIM = 67 => "Too many processes",
+        EUSERS = 68 => "Too many users",
+        EDQUOT = 69 => "Disc quota exceeded",
+        ESTALE = 70 => "Stale NFS file handle",
+        EREMOTE = 71 => "Too many levels of remote in path",
+        EBADRPC = 72 => "RPC struct is bad",
+        ERPCMISMATCH = 73 => "RPC version wrong",
+        EPROGUNAVAIL = 74 => "RPC prog. not avail",
+        EPROGMISMATCH = 75 => "Program version wrong",
+        EPROCUNAVAIL = 76 => "Bad procedure for program",
+        ENOLCK = 77 => "No locks available",
+        ENOSYS = 78 => "Function not implemented",
+        EFTYPE = 79 => "Inappropriate file type or format",
+        EAUTH = 80 => "Authentication error",
+        ENEEDAUTH = 81 => "Need authenticator",
+        EIDRM = 82 => "Identifier removed",
+        ENOMSG = 83 => "No message of desired type",
+        EOVERFLOW = 84 => "Value too large to be stored in data type",
+        ECANCELED = 85 => "Operation canceled",
+        EILSEQ = 86 => "Illegal byte sequence",
+        ENOATTR = 87 => "Attribute not found",
+        EDOOFUS = 88 => "Programming error",
+        EBADMSG = 89 => "Bad message",
+        EMULTIHOP = 90 => "Multihop attempted",
+        ENOLINK = 91 => "Link has been severed",
+        EPROTO = 92 => "Protocol error",
+        ENOTCAPABLE = 93 => "Capabilities insufficient",
+        ECAPMODE = 94 => "Not permitted in capability mode",
+        ENOTRECOVERABLE = 95 => "State not recoverable",
+        EOWNERDEAD = 96 => "Previous owner died",
+        EINTEGRITY = 97 => "Integrity check failed",
     }
 }
 
 impl BsdError {
-    /// Create BsdError from errno in current thread. Returns None of error is
-    /// not known or there is no error at all.
-    pub fn from_errno() -> Option<BsdError> {
-        let e = errno();
-        BsdError::from_i32(e.0 as i32)
-    }
-}
-impl Error for BsdError {
-    fn description(&self) -> &str {
+    /// Full explanation of the error, as found in intro(2).
+    pub fn long_description(&self) -> &'static str {
         match *self {
             BsdError::EPERM => {
                 "Operation not permitted. An attempt was made to perform an operation limited to \
@@ -229,7 +304,7 @@ impl Error for BsdError {
                  the system or no implementation for it exists."
             }
             BsdError::EAFNOSUPPORT => {
-                "Address family not  supported by protocol family. An address incompatible with \
+                "Address family not supported by protocol family. An address incompatible with \
                  the requested protocol was used. For example, you should not necessarily expect \
                  to be able to use NS addresses with ARPA Internet protocols."
             }
@@ -250,7 +325,7 @@ impl Error for BsdError {
                  rebooted."
             }
             BsdError::ECONNABORTED => {
-                "Software caused connectionabort. A connection abort was caused internal to your \
+                "Software caused connection abort. A connection abort was caused internal to your \
                  host machine."
             }
             BsdError::ECONNRESET => {
@@ -268,7 +343,7 @@ impl Error for BsdError {
                  specified a destination when already connected."
             }
             BsdError::ENOTCONN => {
-                "Socket  is not connected. An request to send or receive data was disallowed \
+                "Socket is not connected. An request to send or receive data was disallowed \
                  because the socket was not connected and (when sending on a datagram socket) no \
                  address was supplied."
             }
@@ -276,6 +351,10 @@ impl Error for BsdError {
                 "Can't send after socket shutdown. A request to send data was disallowed because \
                  the socket had already been shut down with a previous shutdown(2) call."
             }
+            BsdError::ETOOMANYREFS => {
+                "Too many references: can't splice. This error code is unused, but present for \
+                 compatibility with other systems."
+            }
             BsdError::ETIMEDOUT => {
                 "Operation timed out. A connect(2) or send(2) request failed because the connected \
                  party did not properly respond after a period of time.  (The timeout period is \
@@ -319,6 +398,10 @@ impl Error for BsdError {
                  indicate the file was deleted on the NFS server or some other catastrophic event \
                  occurred."
             }
+            BsdError::EREMOTE => {
+                "Too many levels of remote in path. An attempt was made to remotely mount a file \
+                 system into a path which already has a remotely mounted component."
+            }
             BsdError::EBADRPC => "RPC struct is bad. Exchange of RPC information was unsuccessful.",
             BsdError::ERPCMISMATCH => {
                 "RPC version wrong. The version of RPC on the remote peer is not compatible with \
@@ -407,22 +490,82 @@ impl Error for BsdError {
                 "Previous owner died. The owner of a robust mutex terminated while holding the \
                  mutex lock."
             }
+            BsdError::EINTEGRITY => {
+                "Integrity check failed. An integrity check such as a check-hash or a \
+                 cross-correlation failed. The integrity error falls in the kernel I/O stack \
+                 between EINVAL that identifies errors in parameters to a system call and EIO \
+                 that identifies errors with the underlying storage media."
+            }
+            BsdError::Unknown(_) => {
+                "Unknown error. The error code is not known to this library, it may come from a \
+                 newer kernel."
+            }
+            BsdError::Host(_) => {
+                "Unknown error. The error code comes from the host OS and has no counterpart on \
+                 FreeBSD."
+            }
         }
     }
-    fn cause(&self) -> Option<&Error> {
-        None
+}
+
+#[test]
+fn bsd_error() {
+    assert_eq!(BsdError::from_i32(1), Some(BsdError::EPERM));
+    assert_eq!(BsdError::from_i32(1000), None);
+    assert_eq!(BsdError::from_errno(), None);
+    BsdError::ENOENT.set_errno();
+    assert_eq!(BsdError::from_errno(), Some(BsdError::ENOENT));
+    BsdError::clear_errno();
+    assert_eq!(BsdError::from_errno(), None);
+
+    assert_eq!(BsdError::from_raw(0), None);
+    assert_eq!(BsdError::from_raw(59), Some(BsdError::ETOOMANYREFS));
+    assert_eq!(BsdError::from_raw(79), Some(BsdError::EFTYPE));
+    assert_eq!(BsdError::from_raw(1000), Some(BsdError::Unknown(1000)));
+    for code in -5..200 {
+        if let Some(err) = BsdError::from_raw(code) {
+            assert_eq!(err.raw_os_error(), code);
+        }
     }
+    assert_eq!(BsdError::Unknown(1000).to_string(), "UNKNOWN(1000): Unknown error");
 }
 
-impl Display for BsdError {
-    fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(f, "{}: {}", self, self.description())
+#[test]
+fn bsd_error_display() {
+    for err in BsdError::all() {
+        assert_eq!(err.name(), format!("{:?}", err));
+        assert!(err.long_description().starts_with(err.short_message()), "{:?}", err);
+        assert_eq!(err.to_string(), format!("{}: {}", err.name(), err.short_message()));
+        assert_eq!(format!("{:#}", err), format!("{}: {}", err.name(), err.long_description()));
     }
+    assert_eq!(BsdError::ENOENT.to_string(), "ENOENT: No such file or directory");
 }
 
+#[test]
+fn bsd_error_from_str() {
+    assert_eq!("ENOENT".parse(), Ok(BsdError::ENOENT));
+    assert_eq!("edquot".parse(), Ok(BsdError::EDQUOT));
+    assert_eq!(" 2 ".parse(), Ok(BsdError::ENOENT));
+    assert_eq!("1000".parse(), Ok(BsdError::Unknown(1000)));
+    assert_eq!(BsdError::ENOENT.name(), "ENOENT");
+
+    let err = "0".parse::<BsdError>().unwrap_err();
+    assert_eq!(err.input(), "0");
+    assert!("EHWPOISON".parse::<BsdError>().is_err());
+    assert!("".parse::<BsdError>().is_err());
+    assert_eq!("EHWPOISON".parse(), Ok(::LinuxError::EHWPOISON));
+}
 
 #[test]
-fn bsd_error() {
-    assert_eq!(BsdError::from_i32(1), Some(BsdError::EPERM));
-    assert_eq!(BsdError::from_errno(), None);
+fn bsd_error_all() {
+    assert_eq!(BsdError::COUNT, 97);
+    assert_eq!(BsdError::MIN_RAW, 1);
+    assert_eq!(BsdError::MAX_RAW, 97);
+
+    let all: Vec<BsdError> = BsdError::all().collect();
+    assert_eq!(all.len(), BsdError::COUNT);
+    let known: Vec<BsdError> = (BsdError::MIN_RAW..BsdError::MAX_RAW + 1)
+        .filter_map(BsdError::from_i32)
+        .collect();
+    assert_eq!(all, known);
 }
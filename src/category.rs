@@ -0,0 +1,104 @@
+use BsdError;
+use BsdError::*;
+
+/// Broad class of an errno, so retry and alerting code doesn't need to keep
+/// its own list of variants.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ErrnoCategory {
+    /// Caller did something wrong, retrying won't help.
+    ProgrammingError,
+    Permission,
+    ResourceExhaustion,
+    /// Worth retrying as is.
+    Transient,
+    Network,
+    Filesystem,
+    Other,
+}
+
+impl BsdError {
+    /// Single category of the error. Some errors fit more than one predicate
+    /// (e.g. ETIMEDOUT is both transient and network), then the first one in
+    /// ErrnoCategory declaration order wins.
+    pub fn category(&self) -> ErrnoCategory {
+        if self.is_programming_error() {
+            ErrnoCategory::ProgrammingError
+        } else if self.is_permission() {
+            ErrnoCategory::Permission
+        } else if self.is_resource_exhaustion() {
+            ErrnoCategory::ResourceExhaustion
+        } else if self.is_transient() {
+            ErrnoCategory::Transient
+        } else if self.is_network() {
+            ErrnoCategory::Network
+        } else if self.is_filesystem() {
+            ErrnoCategory::Filesystem
+        } else {
+            ErrnoCategory::Other
+        }
+    }
+
+    /// Operation may succeed if simply repeated.
+    pub fn is_transient(&self) -> bool {
+        matches!(*self, EAGAIN | EINTR | ETIMEDOUT | ENOBUFS)
+    }
+
+    pub fn is_permission(&self) -> bool {
+        matches!(*self, EPERM | EACCES | ENOTCAPABLE | ECAPMODE)
+    }
+
+    /// Socket, network and RPC errors.
+    pub fn is_network(&self) -> bool {
+        matches!(
+            *self,
+            ENOTSOCK | EDESTADDRREQ | EMSGSIZE | EPROTOTYPE | ENOPROTOOPT | EPROTONOSUPPORT |
+            ESOCKTNOSUPPORT | EPFNOSUPPORT | EAFNOSUPPORT | EADDRINUSE | EADDRNOTAVAIL | ENETDOWN |
+            ENETUNREACH | ENETRESET | ECONNABORTED | ECONNRESET | ENOBUFS | EISCONN | ENOTCONN |
+            ESHUTDOWN | ETOOMANYREFS | ETIMEDOUT | ECONNREFUSED | EHOSTDOWN | EHOSTUNREACH |
+            EALREADY | EINPROGRESS | EBADRPC | ERPCMISMATCH | EPROGUNAVAIL | EPROGMISMATCH |
+            EPROCUNAVAIL | EAUTH | ENEEDAUTH | EPROTO | EMULTIHOP | ENOLINK
+        )
+    }
+
+    /// Errors that come from paths, files and file systems.
+    pub fn is_filesystem(&self) -> bool {
+        matches!(
+            *self,
+            ENOENT | EIO | ENOTBLK | EEXIST | EXDEV | ENOTDIR | EISDIR | ETXTBSY | EFBIG | ENOSPC |
+            ESPIPE | EROFS | EMLINK | ELOOP | ENAMETOOLONG | ENOTEMPTY | EDQUOT | ESTALE |
+            EREMOTE | EFTYPE | ENOATTR | EINTEGRITY
+        )
+    }
+
+    /// System or user is out of something.
+    pub fn is_resource_exhaustion(&self) -> bool {
+        matches!(*self, ENOMEM | ENFILE | EMFILE | ENOSPC | EDQUOT | EPROCLIM | EUSERS)
+    }
+
+    /// API was misused, usually means a bug on our side.
+    pub fn is_programming_error(&self) -> bool {
+        matches!(*self, EDOOFUS | EFAULT | EINVAL)
+    }
+}
+
+#[test]
+fn category() {
+    for err in &[EAGAIN, EINTR, ETIMEDOUT, ENOBUFS] {
+        assert!(err.is_transient(), "{:?}", err);
+    }
+    for err in &[ENOMEM, ENFILE, EMFILE, ENOSPC, EDQUOT] {
+        assert!(err.is_resource_exhaustion(), "{:?}", err);
+        assert_eq!(err.category(), ErrnoCategory::ResourceExhaustion);
+    }
+    for err in &[EPERM, EACCES, ENOTCAPABLE, ECAPMODE] {
+        assert_eq!(err.category(), ErrnoCategory::Permission);
+    }
+    for err in &[EDOOFUS, EFAULT, EINVAL] {
+        assert_eq!(err.category(), ErrnoCategory::ProgrammingError);
+    }
+    assert_eq!(ETIMEDOUT.category(), ErrnoCategory::Transient);
+    assert_eq!(ECONNREFUSED.category(), ErrnoCategory::Network);
+    assert_eq!(ENOENT.category(), ErrnoCategory::Filesystem);
+    assert_eq!(ECHILD.category(), ErrnoCategory::Other);
+    assert_eq!(Unknown(1000).category(), ErrnoCategory::Other);
+}
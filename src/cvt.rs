@@ -0,0 +1,134 @@
+//! Helpers to check libc return values. errno is only read when return value
+//! says the call failed, so stale errno from earlier calls is never picked up.
+//! errno is translated from host numbering, see BsdError::from_host_raw().
+//! If the call failed but left errno at 0, the error is reported as EIO.
+use errno::errno;
+use libc::{c_int, ssize_t};
+
+use {Blunder, BsdError};
+
+#[track_caller]
+fn last_error() -> Blunder<BsdError> {
+    Blunder::new(BsdError::from_host_raw(errno().0).unwrap_or(BsdError::EIO))
+}
+
+/// Check return value of a call that returns -1 on failure.
+///
+/// ```
+/// # extern crate blunder;
+/// # extern crate libc;
+/// # use blunder::{cvt, BsdError};
+/// # fn main() {
+/// let err = cvt(unsafe { libc::close(-1) }).unwrap_err();
+/// assert_eq!(err.kind(), BsdError::EBADF);
+/// # }
+/// ```
+#[track_caller]
+pub fn cvt(ret: c_int) -> Result<c_int, Blunder<BsdError>> {
+    if ret == -1 {
+        Err(last_error())
+    } else {
+        Ok(ret)
+    }
+}
+
+/// Same as cvt(), but for calls returning ssize_t like read(2).
+#[track_caller]
+pub fn cvt_ssize(ret: ssize_t) -> Result<ssize_t, Blunder<BsdError>> {
+    if ret == -1 {
+        Err(last_error())
+    } else {
+        Ok(ret)
+    }
+}
+
+/// Check return value of a call that returns NULL on failure.
+#[track_caller]
+pub fn cvt_ptr<T>(ptr: *mut T) -> Result<*mut T, Blunder<BsdError>> {
+    if ptr.is_null() {
+        Err(last_error())
+    } else {
+        Ok(ptr)
+    }
+}
+
+/// Same as cvt(), but call is repeated as long as it fails with EINTR.
+#[track_caller]
+pub fn cvt_r<F>(mut f: F) -> Result<c_int, Blunder<BsdError>>
+where
+    F: FnMut() -> c_int,
+{
+    loop {
+        match cvt(f()) {
+            Err(ref err) if err.kind() == BsdError::EINTR => {}
+            other => return other,
+        }
+    }
+}
+
+impl BsdError {
+    /// Check return code of APIs that return error number instead of setting
+    /// errno, e.g. pthread_*, posix_spawn, posix_fallocate or getpwnam_r.
+    /// Anything but 0 is an error.
+    #[track_caller]
+    pub fn check_rc(rc: c_int) -> Result<(), Blunder<BsdError>> {
+        match BsdError::from_host_raw(rc) {
+            None => Ok(()),
+            Some(err) => Err(Blunder::new(err)),
+        }
+    }
+}
+
+#[test]
+fn cvt_errors() {
+    use errno::{set_errno, Errno};
+    use std::ptr;
+
+    set_errno(Errno(libc::EPERM));
+    assert_eq!(cvt(0), Ok(0));
+    assert_eq!(cvt_ssize(10), Ok(10));
+
+    let err = cvt(unsafe { libc::close(-1) }).unwrap_err();
+    assert_eq!(err.kind(), BsdError::EBADF);
+    assert_eq!(err.location().unwrap().line(), line!() - 2);
+
+    let mut buf = [0u8; 1];
+    let ret = unsafe { libc::read(-1, buf.as_mut_ptr() as *mut libc::c_void, 1) };
+    assert_eq!(cvt_ssize(ret).unwrap_err().kind(), BsdError::EBADF);
+
+    set_errno(Errno(libc::ENOMEM));
+    let err = cvt_ptr(ptr::null_mut::<u8>()).unwrap_err();
+    assert_eq!(err.kind(), BsdError::ENOMEM);
+
+    let mut calls = 0;
+    let res = cvt_r(|| {
+        calls += 1;
+        if calls < 3 {
+            set_errno(Errno(libc::EINTR));
+            -1
+        } else {
+            42
+        }
+    });
+    assert_eq!((res, calls), (Ok(42), 3));
+
+    set_errno(Errno(0));
+    assert_eq!(cvt(-1).unwrap_err().kind(), BsdError::EIO);
+
+    // ECHRNG is Linux only, it must not turn into FreeBSD 44 on the way back
+    #[cfg(target_os = "linux")]
+    {
+        set_errno(Errno(libc::ECHRNG));
+        let err = cvt(-1).unwrap_err().kind();
+        assert_eq!(err, BsdError::Host(libc::ECHRNG));
+        assert_eq!(err.to_string(), "UNKNOWN(linux:44): Unknown error");
+        assert_eq!("linux:44".parse(), Ok(err));
+        assert_eq!(err.to_host_raw(), Some(libc::ECHRNG));
+    }
+}
+
+#[test]
+fn check_rc() {
+    assert!(BsdError::check_rc(0).is_ok());
+    assert_eq!(BsdError::check_rc(libc::EAGAIN).unwrap_err().kind(), BsdError::EAGAIN);
+}
@@ -0,0 +1,170 @@
+use std::error::Error as StdError;
+use std::fmt;
+use std::io::{self, IsTerminal};
+use std::process::{ExitCode, Termination};
+
+use Blunder;
+use BsdError;
+use BsdError::*;
+
+/// Exit codes from sysexits(3).
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum SysExit {
+    EX_OK = 0,
+    EX_USAGE = 64,
+    EX_DATAERR = 65,
+    EX_NOINPUT = 66,
+    EX_NOUSER = 67,
+    EX_NOHOST = 68,
+    EX_UNAVAILABLE = 69,
+    EX_SOFTWARE = 70,
+    EX_OSERR = 71,
+    EX_OSFILE = 72,
+    EX_CANTCREAT = 73,
+    EX_IOERR = 74,
+    EX_TEMPFAIL = 75,
+    EX_PROTOCOL = 76,
+    EX_NOPERM = 77,
+    EX_CONFIG = 78,
+}
+
+impl SysExit {
+    pub fn code(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Symbolic name, e.g. "EX_USAGE".
+    pub fn name(&self) -> &'static str {
+        match *self {
+            SysExit::EX_OK => "EX_OK",
+            SysExit::EX_USAGE => "EX_USAGE",
+            SysExit::EX_DATAERR => "EX_DATAERR",
+            SysExit::EX_NOINPUT => "EX_NOINPUT",
+            SysExit::EX_NOUSER => "EX_NOUSER",
+            SysExit::EX_NOHOST => "EX_NOHOST",
+            SysExit::EX_UNAVAILABLE => "EX_UNAVAILABLE",
+            SysExit::EX_SOFTWARE => "EX_SOFTWARE",
+            SysExit::EX_OSERR => "EX_OSERR",
+            SysExit::EX_OSFILE => "EX_OSFILE",
+            SysExit::EX_CANTCREAT => "EX_CANTCREAT",
+            SysExit::EX_IOERR => "EX_IOERR",
+            SysExit::EX_TEMPFAIL => "EX_TEMPFAIL",
+            SysExit::EX_PROTOCOL => "EX_PROTOCOL",
+            SysExit::EX_NOPERM => "EX_NOPERM",
+            SysExit::EX_CONFIG => "EX_CONFIG",
+        }
+    }
+}
+
+impl fmt::Display for SysExit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}({})", self.name(), self.code())
+    }
+}
+
+impl From<SysExit> for ExitCode {
+    fn from(exit: SysExit) -> ExitCode {
+        ExitCode::from(exit.code())
+    }
+}
+
+/// Error kind that maps to a process exit code.
+pub trait SysExitCode {
+    fn sys_exit(&self) -> SysExit;
+}
+
+/// Mapping follows how FreeBSD base utilities pick their exit codes. OS
+/// errors without a better match are EX_OSERR.
+impl SysExitCode for BsdError {
+    fn sys_exit(&self) -> SysExit {
+        if self.is_transient() {
+            return SysExit::EX_TEMPFAIL;
+        }
+        match *self {
+            EINVAL | EDOM | ERANGE | EILSEQ | EFTYPE | EBADMSG => SysExit::EX_DATAERR,
+            ENOENT | ENOTDIR => SysExit::EX_NOINPUT,
+            EHOSTDOWN | EHOSTUNREACH => SysExit::EX_NOHOST,
+            ENOSYS | EOPNOTSUPP | EPROTONOSUPPORT | EAFNOSUPPORT | ENETUNREACH |
+            ECONNREFUSED => SysExit::EX_UNAVAILABLE,
+            EDOOFUS => SysExit::EX_SOFTWARE,
+            EEXIST | EISDIR | EROFS | ENOSPC | EDQUOT => SysExit::EX_CANTCREAT,
+            EIO | EINTEGRITY => SysExit::EX_IOERR,
+            EPROTO | EBADRPC | ERPCMISMATCH | EPROGMISMATCH | EPROGUNAVAIL | EPROCUNAVAIL => {
+                SysExit::EX_PROTOCOL
+            }
+            EPERM | EACCES | ENOTCAPABLE | ECAPMODE | EAUTH | ENEEDAUTH => SysExit::EX_NOPERM,
+            _ => SysExit::EX_OSERR,
+        }
+    }
+}
+
+/// Exit code of the kind.
+impl<T: SysExitCode + StdError + Clone> SysExitCode for Blunder<T> {
+    fn sys_exit(&self) -> SysExit {
+        self.kind.sys_exit()
+    }
+}
+
+/// Return type for main(): on error prints Blunder::report() to stderr (in
+/// color if it's a terminal) and exits with the code from SysExitCode.
+///
+/// ```no_run
+/// # use blunder::{Blunder, BlunderMain, BsdError};
+/// fn run() -> Result<(), Blunder<BsdError>> {
+///     Err(Blunder::with_detail(BsdError::ENOENT, "can't open /etc/app.conf"))
+/// }
+///
+/// fn main() -> BlunderMain<BsdError> {
+///     run().into()
+/// }
+/// ```
+#[derive(Debug)]
+pub struct BlunderMain<T: StdError + Clone>(pub Result<(), Blunder<T>>);
+
+impl<T: StdError + Clone> From<Result<(), Blunder<T>>> for BlunderMain<T> {
+    fn from(result: Result<(), Blunder<T>>) -> BlunderMain<T> {
+        BlunderMain(result)
+    }
+}
+
+impl<T: SysExitCode + StdError + Clone> BlunderMain<T> {
+    /// Code process is going to exit with.
+    pub fn sys_exit(&self) -> SysExit {
+        match self.0 {
+            Ok(()) => SysExit::EX_OK,
+            Err(ref err) => err.sys_exit(),
+        }
+    }
+}
+
+impl<T: SysExitCode + StdError + Clone + 'static> Termination for BlunderMain<T> {
+    fn report(self) -> ExitCode {
+        if let Err(ref err) = self.0 {
+            let stderr = io::stderr();
+            eprint!("{}", err.report().color(stderr.is_terminal()));
+        }
+        self.sys_exit().into()
+    }
+}
+
+#[test]
+fn sys_exit() {
+    assert_eq!(BsdError::ENOENT.sys_exit(), SysExit::EX_NOINPUT);
+    assert_eq!(BsdError::EACCES.sys_exit(), SysExit::EX_NOPERM);
+    assert_eq!(BsdError::EAGAIN.sys_exit(), SysExit::EX_TEMPFAIL);
+    assert_eq!(BsdError::EIO.sys_exit(), SysExit::EX_IOERR);
+    assert_eq!(BsdError::EINVAL.sys_exit(), SysExit::EX_DATAERR);
+    assert_eq!(BsdError::ENOMEM.sys_exit(), SysExit::EX_OSERR);
+    assert_eq!(BsdError::Unknown(1000).sys_exit(), SysExit::EX_OSERR);
+    assert_eq!(SysExit::EX_CONFIG.code(), 78);
+    assert_eq!(SysExit::EX_USAGE.to_string(), "EX_USAGE(64)");
+
+    let ok: BlunderMain<BsdError> = Ok(()).into();
+    assert_eq!(ok.sys_exit(), SysExit::EX_OK);
+    assert_eq!(ok.report(), ExitCode::SUCCESS);
+
+    let err: BlunderMain<BsdError> = Err(Blunder::new(BsdError::EPERM)).into();
+    assert_eq!(err.sys_exit(), SysExit::EX_NOPERM);
+    assert_eq!(err.report(), ExitCode::from(77));
+}
@@ -0,0 +1,131 @@
+use std::error::Error as StdError;
+
+use Blunder;
+
+/// Extension for Result to attach context to the error on the way out.
+///
+/// ```
+/// # use blunder::{Blunder, BsdError, ResultExt};
+/// fn handshake() -> Result<(), Blunder<BsdError>> {
+///     Err(BsdError::EPROTO).detail("client doesn't understand XXX cipher")
+/// }
+/// assert_eq!(handshake().unwrap_err().kind(), BsdError::EPROTO);
+/// ```
+pub trait ResultExt<T, E: StdError + Clone> {
+    /// Wrap error into Blunder with given detail message. Existing detail is
+    /// replaced.
+    fn detail<S: Into<String>>(self, detail: S) -> Result<T, Blunder<E>>;
+
+    /// Same as detail(), but message is only built when there is an error.
+    fn with_detail<S, F>(self, f: F) -> Result<T, Blunder<E>>
+    where
+        S: Into<String>,
+        F: FnOnce() -> S;
+
+    /// Wrap error into Blunder of another kind, original error is kept as
+    /// its source.
+    fn blunder_kind<K: StdError + Clone>(self, kind: K) -> Result<T, Blunder<K>>;
+}
+
+impl<T, E> ResultExt<T, E> for Result<T, E>
+where
+    E: StdError + Clone + Send + Sync + 'static,
+{
+    #[track_caller]
+    fn detail<S: Into<String>>(self, detail: S) -> Result<T, Blunder<E>> {
+        match self {
+            Ok(value) => Ok(value),
+            Err(err) => Err(Blunder::with_detail(err, detail)),
+        }
+    }
+
+    #[track_caller]
+    fn with_detail<S, F>(self, f: F) -> Result<T, Blunder<E>>
+    where
+        S: Into<String>,
+        F: FnOnce() -> S,
+    {
+        match self {
+            Ok(value) => Ok(value),
+            Err(err) => Err(Blunder::with_detail(err, f())),
+        }
+    }
+
+    #[track_caller]
+    fn blunder_kind<K: StdError + Clone>(self, kind: K) -> Result<T, Blunder<K>> {
+        match self {
+            Ok(value) => Ok(value),
+            Err(err) => Err(Blunder::caused_by(kind, err)),
+        }
+    }
+}
+
+impl<T, E> ResultExt<T, E> for Result<T, Blunder<E>>
+where
+    E: StdError + Clone + Send + Sync + 'static,
+{
+    fn detail<S: Into<String>>(self, detail: S) -> Result<T, Blunder<E>> {
+        self.map_err(|mut err| {
+            err.set_detail(detail);
+            err
+        })
+    }
+
+    fn with_detail<S, F>(self, f: F) -> Result<T, Blunder<E>>
+    where
+        S: Into<String>,
+        F: FnOnce() -> S,
+    {
+        self.map_err(|mut err| {
+            err.set_detail(f());
+            err
+        })
+    }
+
+    #[track_caller]
+    fn blunder_kind<K: StdError + Clone>(self, kind: K) -> Result<T, Blunder<K>> {
+        match self {
+            Ok(value) => Ok(value),
+            Err(err) => Err(Blunder::caused_by(kind, err)),
+        }
+    }
+}
+
+/// Extension for Option to turn None into Blunder.
+pub trait OptionExt<T> {
+    fn ok_or_blunder<K: StdError + Clone>(self, kind: K) -> Result<T, Blunder<K>>;
+}
+
+impl<T> OptionExt<T> for Option<T> {
+    #[track_caller]
+    fn ok_or_blunder<K: StdError + Clone>(self, kind: K) -> Result<T, Blunder<K>> {
+        match self {
+            Some(value) => Ok(value),
+            None => Err(Blunder::new(kind)),
+        }
+    }
+}
+
+#[test]
+fn result_ext() {
+    use BsdError;
+
+    let res: Result<(), BsdError> = Err(BsdError::ENOENT);
+    let err = res.with_detail(|| format!("{} is missing", "/etc/rc.conf")).unwrap_err();
+    assert_eq!(err.location().unwrap().line(), line!() - 1);
+    assert_eq!(err.kind(), BsdError::ENOENT);
+    assert_eq!(err.detail(), Some("/etc/rc.conf is missing".to_owned()));
+
+    let err = Err::<(), _>(err).detail("config").blunder_kind(BsdError::EIO).unwrap_err();
+    assert_eq!(err.kind(), BsdError::EIO);
+    assert_eq!(err.detail(), None);
+    let source = err.source().unwrap().downcast_ref::<Blunder<BsdError>>().unwrap();
+    assert_eq!(source.kind(), BsdError::ENOENT);
+    assert_eq!(source.detail(), Some("config".to_owned()));
+
+    assert_eq!(Ok::<u8, BsdError>(1).detail("never built"), Ok(1));
+
+    let err = None::<u8>.ok_or_blunder(BsdError::ESRCH).unwrap_err();
+    assert_eq!(err, Blunder::new(BsdError::ESRCH));
+    assert_eq!(Some(1).ok_or_blunder(BsdError::ESRCH), Ok(1));
+}
@@ -0,0 +1,235 @@
+use libc::{self, c_int};
+use std::error::Error;
+use std::fmt;
+
+use BsdError;
+
+/// Errors returned by getaddrinfo(3) and getnameinfo(3). These don't go
+/// through errno, except for EAI_SYSTEM, which is resolved to the BsdError
+/// from errno as soon as the code is converted. Numbering is the host one,
+/// since resolver codes never leave the machine.
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum GaiError {
+    EAI_AGAIN,
+    EAI_BADFLAGS,
+    EAI_FAIL,
+    EAI_FAMILY,
+    EAI_MEMORY,
+    EAI_NONAME,
+    EAI_SERVICE,
+    EAI_SOCKTYPE,
+    /// System error, errno at the time of conversion. EIO if errno was 0.
+    EAI_SYSTEM(BsdError),
+    EAI_OVERFLOW,
+    /// Code that is not known to this crate.
+    Unknown(i32),
+}
+
+impl GaiError {
+    /// Create error from getaddrinfo(3) return value. Returns None only for
+    /// success. EAI_SYSTEM reads errno of current thread, so call it right
+    /// after the failed call.
+    ///
+    /// ```
+    /// # use blunder::{BsdError, GaiError};
+    /// BsdError::EMFILE.set_errno();
+    /// let err = GaiError::from_raw(libc::EAI_SYSTEM).unwrap();
+    /// assert_eq!(err, GaiError::EAI_SYSTEM(BsdError::EMFILE));
+    /// ```
+    pub fn from_raw(code: c_int) -> Option<GaiError> {
+        let err = match code {
+            0 => return None,
+            libc::EAI_AGAIN => GaiError::EAI_AGAIN,
+            libc::EAI_BADFLAGS => GaiError::EAI_BADFLAGS,
+            libc::EAI_FAIL => GaiError::EAI_FAIL,
+            libc::EAI_FAMILY => GaiError::EAI_FAMILY,
+            libc::EAI_MEMORY => GaiError::EAI_MEMORY,
+            libc::EAI_NONAME => GaiError::EAI_NONAME,
+            libc::EAI_SERVICE => GaiError::EAI_SERVICE,
+            libc::EAI_SOCKTYPE => GaiError::EAI_SOCKTYPE,
+            libc::EAI_SYSTEM => {
+                let errno = ::errno::errno().0;
+                GaiError::EAI_SYSTEM(BsdError::from_host_raw(errno).unwrap_or(BsdError::EIO))
+            }
+            libc::EAI_OVERFLOW => GaiError::EAI_OVERFLOW,
+            code => GaiError::Unknown(code),
+        };
+        Some(err)
+    }
+
+    /// Numeric value of the error, suitable to hand back to C.
+    pub fn raw(&self) -> c_int {
+        match *self {
+            GaiError::EAI_AGAIN => libc::EAI_AGAIN,
+            GaiError::EAI_BADFLAGS => libc::EAI_BADFLAGS,
+            GaiError::EAI_FAIL => libc::EAI_FAIL,
+            GaiError::EAI_FAMILY => libc::EAI_FAMILY,
+            GaiError::EAI_MEMORY => libc::EAI_MEMORY,
+            GaiError::EAI_NONAME => libc::EAI_NONAME,
+            GaiError::EAI_SERVICE => libc::EAI_SERVICE,
+            GaiError::EAI_SOCKTYPE => libc::EAI_SOCKTYPE,
+            GaiError::EAI_SYSTEM(_) => libc::EAI_SYSTEM,
+            GaiError::EAI_OVERFLOW => libc::EAI_OVERFLOW,
+            GaiError::Unknown(code) => code,
+        }
+    }
+
+    /// Errno behind EAI_SYSTEM.
+    pub fn system_error(&self) -> Option<BsdError> {
+        match *self {
+            GaiError::EAI_SYSTEM(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    /// Symbolic name of the error, e.g. "EAI_NONAME".
+    pub fn name(&self) -> &'static str {
+        match *self {
+            GaiError::EAI_AGAIN => "EAI_AGAIN",
+            GaiError::EAI_BADFLAGS => "EAI_BADFLAGS",
+            GaiError::EAI_FAIL => "EAI_FAIL",
+            GaiError::EAI_FAMILY => "EAI_FAMILY",
+            GaiError::EAI_MEMORY => "EAI_MEMORY",
+            GaiError::EAI_NONAME => "EAI_NONAME",
+            GaiError::EAI_SERVICE => "EAI_SERVICE",
+            GaiError::EAI_SOCKTYPE => "EAI_SOCKTYPE",
+            GaiError::EAI_SYSTEM(_) => "EAI_SYSTEM",
+            GaiError::EAI_OVERFLOW => "EAI_OVERFLOW",
+            GaiError::Unknown(_) => "UNKNOWN",
+        }
+    }
+
+    /// Short gai_strerror(3)-like message.
+    pub fn short_message(&self) -> &'static str {
+        match *self {
+            GaiError::EAI_AGAIN => "Temporary failure in name resolution",
+            GaiError::EAI_BADFLAGS => "Invalid value for ai_flags",
+            GaiError::EAI_FAIL => "Non-recoverable failure in name resolution",
+            GaiError::EAI_FAMILY => "ai_family not supported",
+            GaiError::EAI_MEMORY => "Memory allocation failure",
+            GaiError::EAI_NONAME => "hostname nor servname provided, or not known",
+            GaiError::EAI_SERVICE => "servname not supported for ai_socktype",
+            GaiError::EAI_SOCKTYPE => "ai_socktype not supported",
+            GaiError::EAI_SYSTEM(_) => "System error returned in errno",
+            GaiError::EAI_OVERFLOW => "Argument buffer overflow",
+            GaiError::Unknown(_) => "Unknown error",
+        }
+    }
+
+    /// Full explanation of the error, as found in getaddrinfo(3).
+    pub fn long_description(&self) -> &'static str {
+        match *self {
+            GaiError::EAI_AGAIN => {
+                "Temporary failure in name resolution. The name server returned a temporary \
+                 failure indication, try again later."
+            }
+            GaiError::EAI_BADFLAGS => "Invalid value for ai_flags. The flags in hints are invalid.",
+            GaiError::EAI_FAIL => {
+                "Non-recoverable failure in name resolution. The name server returned a \
+                 permanent failure indication."
+            }
+            GaiError::EAI_FAMILY => {
+                "ai_family not supported. The requested address family is not supported."
+            }
+            GaiError::EAI_MEMORY => "Memory allocation failure. Out of memory.",
+            GaiError::EAI_NONAME => {
+                "hostname nor servname provided, or not known. The node or service is not \
+                 known, or both of them are NULL."
+            }
+            GaiError::EAI_SERVICE => {
+                "servname not supported for ai_socktype. The requested service is not \
+                 available for the requested socket type."
+            }
+            GaiError::EAI_SOCKTYPE => {
+                "ai_socktype not supported. The requested socket type is not supported."
+            }
+            GaiError::EAI_SYSTEM(_) => {
+                "System error returned in errno. Some other system error occurred, errno \
+                 has the details."
+            }
+            GaiError::EAI_OVERFLOW => {
+                "Argument buffer overflow. The buffer supplied to getnameinfo(3) was too small."
+            }
+            GaiError::Unknown(_) => "Unknown error",
+        }
+    }
+
+    /// Worth trying again: EAI_AGAIN, or EAI_SYSTEM with transient errno.
+    pub fn is_transient(&self) -> bool {
+        match *self {
+            GaiError::EAI_AGAIN => true,
+            GaiError::EAI_SYSTEM(err) => err.is_transient(),
+            _ => false,
+        }
+    }
+}
+
+impl Error for GaiError {
+    fn description(&self) -> &str {
+        self.long_description()
+    }
+
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            GaiError::EAI_SYSTEM(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Prints "EAI_NONAME: hostname nor servname provided, or not known", same as
+/// BsdError. EAI_SYSTEM prints the errno as well.
+impl fmt::Display for GaiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let GaiError::Unknown(code) = *self {
+            write!(f, "{}({})", self.name(), code)?;
+        } else {
+            f.write_str(self.name())?;
+        }
+        if f.alternate() {
+            write!(f, ": {}", self.long_description())?;
+        } else {
+            write!(f, ": {}", self.short_message())?;
+        }
+        if let GaiError::EAI_SYSTEM(err) = *self {
+            write!(f, " ({})", err)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn gai_error() {
+    use std::ptr;
+
+    assert_eq!(GaiError::from_raw(0), None);
+    assert_eq!(GaiError::from_raw(libc::EAI_NONAME), Some(GaiError::EAI_NONAME));
+    assert_eq!(GaiError::from_raw(12345), Some(GaiError::Unknown(12345)));
+    assert_eq!(GaiError::EAI_AGAIN.raw(), libc::EAI_AGAIN);
+    assert!(GaiError::EAI_AGAIN.is_transient());
+    assert!(!GaiError::EAI_NONAME.is_transient());
+    assert_eq!(
+        GaiError::EAI_NONAME.to_string(),
+        "EAI_NONAME: hostname nor servname provided, or not known"
+    );
+
+    BsdError::EINTR.set_errno();
+    let err = GaiError::from_raw(libc::EAI_SYSTEM).unwrap();
+    BsdError::clear_errno();
+    assert_eq!(err.system_error(), Some(BsdError::EINTR));
+    assert!(err.is_transient());
+    assert_eq!(err.raw(), libc::EAI_SYSTEM);
+    assert_eq!(
+        err.to_string(),
+        "EAI_SYSTEM: System error returned in errno (EINTR: Interrupted system call)"
+    );
+    assert!(err.source().is_some());
+    let err = GaiError::from_raw(libc::EAI_SYSTEM).unwrap();
+    assert_eq!(err.system_error(), Some(BsdError::EIO));
+
+    // Neither node nor service given
+    let mut res = ptr::null_mut();
+    let rc = unsafe { libc::getaddrinfo(ptr::null(), ptr::null(), ptr::null(), &mut res) };
+    assert_eq!(GaiError::from_raw(rc), Some(GaiError::EAI_NONAME));
+}
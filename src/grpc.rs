@@ -0,0 +1,220 @@
+use std::error::Error as StdError;
+use std::fmt;
+
+use Blunder;
+use BsdError;
+use BsdError::*;
+
+/// Canonical gRPC status codes, numbered as on the wire.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum GrpcCode {
+    Ok = 0,
+    Cancelled = 1,
+    Unknown = 2,
+    InvalidArgument = 3,
+    DeadlineExceeded = 4,
+    NotFound = 5,
+    AlreadyExists = 6,
+    PermissionDenied = 7,
+    ResourceExhausted = 8,
+    FailedPrecondition = 9,
+    Aborted = 10,
+    OutOfRange = 11,
+    Unimplemented = 12,
+    Internal = 13,
+    Unavailable = 14,
+    DataLoss = 15,
+    Unauthenticated = 16,
+}
+
+impl GrpcCode {
+    pub fn code(&self) -> i32 {
+        *self as i32
+    }
+
+    /// Name as in the gRPC spec, e.g. "NOT_FOUND".
+    pub fn name(&self) -> &'static str {
+        match *self {
+            GrpcCode::Ok => "OK",
+            GrpcCode::Cancelled => "CANCELLED",
+            GrpcCode::Unknown => "UNKNOWN",
+            GrpcCode::InvalidArgument => "INVALID_ARGUMENT",
+            GrpcCode::DeadlineExceeded => "DEADLINE_EXCEEDED",
+            GrpcCode::NotFound => "NOT_FOUND",
+            GrpcCode::AlreadyExists => "ALREADY_EXISTS",
+            GrpcCode::PermissionDenied => "PERMISSION_DENIED",
+            GrpcCode::ResourceExhausted => "RESOURCE_EXHAUSTED",
+            GrpcCode::FailedPrecondition => "FAILED_PRECONDITION",
+            GrpcCode::Aborted => "ABORTED",
+            GrpcCode::OutOfRange => "OUT_OF_RANGE",
+            GrpcCode::Unimplemented => "UNIMPLEMENTED",
+            GrpcCode::Internal => "INTERNAL",
+            GrpcCode::Unavailable => "UNAVAILABLE",
+            GrpcCode::DataLoss => "DATA_LOSS",
+            GrpcCode::Unauthenticated => "UNAUTHENTICATED",
+        }
+    }
+}
+
+impl fmt::Display for GrpcCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(feature = "tonic")]
+impl From<GrpcCode> for ::tonic::Code {
+    fn from(code: GrpcCode) -> ::tonic::Code {
+        ::tonic::Code::from_i32(code.code())
+    }
+}
+
+/// Error kind that maps to a gRPC status code.
+pub trait GrpcStatus {
+    fn grpc_code(&self) -> GrpcCode;
+
+    /// Reason for google.rpc.ErrorInfo in status details, UPPER_SNAKE_CASE.
+    /// None means no details.
+    fn grpc_reason(&self) -> Option<String> {
+        None
+    }
+
+    /// Domain the reason belongs to.
+    fn grpc_domain(&self) -> String {
+        String::new()
+    }
+}
+
+/// Reason is the errno name, e.g. "ENOENT", in "errno" domain. Errors
+/// without an obvious counterpart are UNKNOWN.
+impl GrpcStatus for BsdError {
+    fn grpc_code(&self) -> GrpcCode {
+        match *self {
+            ECANCELED => GrpcCode::Cancelled,
+            EINVAL | EDOM | E2BIG | ENAMETOOLONG | EILSEQ => GrpcCode::InvalidArgument,
+            ETIMEDOUT => GrpcCode::DeadlineExceeded,
+            ENOENT | ESRCH | ENXIO | ENODEV => GrpcCode::NotFound,
+            EEXIST | EADDRINUSE | EALREADY => GrpcCode::AlreadyExists,
+            EPERM | EACCES | EROFS | ENOTCAPABLE | ECAPMODE => GrpcCode::PermissionDenied,
+            ENOMEM | ENOSPC | EDQUOT | EMFILE | ENFILE | ENOBUFS | EPROCLIM | EUSERS => {
+                GrpcCode::ResourceExhausted
+            }
+            ENOTEMPTY | ENOTDIR | EISDIR | EXDEV | EBADF | ENOTCONN | EISCONN => {
+                GrpcCode::FailedPrecondition
+            }
+            EDEADLK => GrpcCode::Aborted,
+            ERANGE | EOVERFLOW | EFBIG => GrpcCode::OutOfRange,
+            ENOSYS | EOPNOTSUPP | EPROTONOSUPPORT | EAFNOSUPPORT | ENOTTY => {
+                GrpcCode::Unimplemented
+            }
+            EFAULT | EDOOFUS => GrpcCode::Internal,
+            EAGAIN | EINTR | EBUSY | ECONNREFUSED | ECONNRESET | ECONNABORTED | ENETDOWN |
+            ENETUNREACH | ENETRESET | EHOSTDOWN | EHOSTUNREACH | EPIPE => GrpcCode::Unavailable,
+            EINTEGRITY => GrpcCode::DataLoss,
+            EAUTH | ENEEDAUTH => GrpcCode::Unauthenticated,
+            _ => GrpcCode::Unknown,
+        }
+    }
+
+    fn grpc_reason(&self) -> Option<String> {
+        Some(self.name().to_owned())
+    }
+
+    fn grpc_domain(&self) -> String {
+        "errno".to_owned()
+    }
+}
+
+/// Code and details of the kind.
+impl<T: GrpcStatus + StdError + Clone> GrpcStatus for Blunder<T> {
+    fn grpc_code(&self) -> GrpcCode {
+        self.kind.grpc_code()
+    }
+
+    fn grpc_reason(&self) -> Option<String> {
+        self.kind.grpc_reason()
+    }
+
+    fn grpc_domain(&self) -> String {
+        self.kind.grpc_domain()
+    }
+}
+
+/// Message is the detail, or Display of the kind if there is none. Details
+/// are google.rpc.Status with ErrorInfo made of GrpcStatus::grpc_reason() and
+/// grpc_domain(), read them with tonic_types::StatusExt.
+#[cfg(feature = "tonic")]
+impl<T: GrpcStatus + StdError + Clone> From<Blunder<T>> for ::tonic::Status {
+    fn from(blunder: Blunder<T>) -> ::tonic::Status {
+        use std::collections::HashMap;
+        use tonic_types::{ErrorDetails, StatusExt};
+
+        let code = blunder.grpc_code().into();
+        let message = blunder.detail().unwrap_or_else(|| blunder.kind.to_string());
+        match blunder.grpc_reason() {
+            Some(reason) => {
+                let domain = blunder.grpc_domain();
+                let info = ErrorDetails::with_error_info(reason, domain, HashMap::new());
+                ::tonic::Status::with_error_details(code, message, info)
+            }
+            None => ::tonic::Status::new(code, message),
+        }
+    }
+}
+
+#[test]
+fn grpc_status() {
+    assert_eq!(BsdError::ENOENT.grpc_code(), GrpcCode::NotFound);
+    assert_eq!(BsdError::EACCES.grpc_code(), GrpcCode::PermissionDenied);
+    assert_eq!(BsdError::EDQUOT.grpc_code(), GrpcCode::ResourceExhausted);
+    assert_eq!(BsdError::EAGAIN.grpc_code(), GrpcCode::Unavailable);
+    assert_eq!(BsdError::ETIMEDOUT.grpc_code(), GrpcCode::DeadlineExceeded);
+    assert_eq!(BsdError::Unknown(1000).grpc_code(), GrpcCode::Unknown);
+    assert_eq!(GrpcCode::ResourceExhausted.to_string(), "RESOURCE_EXHAUSTED");
+    assert_eq!(GrpcCode::Unauthenticated.code(), 16);
+
+    let err = Blunder::with_detail(BsdError::ENOENT, "no such user");
+    assert_eq!(err.grpc_code(), GrpcCode::NotFound);
+    assert_eq!(err.grpc_reason(), Some("ENOENT".to_owned()));
+    assert_eq!(err.grpc_domain(), "errno");
+}
+
+#[cfg(feature = "tonic")]
+#[test]
+fn tonic_status() {
+    use tonic::{Code, Status};
+    use tonic_types::StatusExt;
+
+    #[derive(Debug, Clone)]
+    struct Busy;
+
+    impl fmt::Display for Busy {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("busy")
+        }
+    }
+
+    impl StdError for Busy {}
+
+    impl GrpcStatus for Busy {
+        fn grpc_code(&self) -> GrpcCode {
+            GrpcCode::Unavailable
+        }
+    }
+
+    let status = Status::from(Blunder::with_detail(BsdError::ENOENT, "no such user"));
+    assert_eq!(status.code(), Code::NotFound);
+    assert_eq!(status.message(), "no such user");
+    let info = status.get_details_error_info().unwrap();
+    assert_eq!(info.reason, "ENOENT");
+    assert_eq!(info.domain, "errno");
+
+    let status = Status::from(Blunder::new(Busy));
+    assert_eq!(status.code(), Code::Unavailable);
+    assert_eq!(status.message(), "busy");
+    assert!(status.details().is_empty());
+
+    assert_eq!(Code::from(GrpcCode::Ok), Code::Ok);
+    assert_eq!(Code::from(GrpcCode::DataLoss), Code::DataLoss);
+    assert_eq!(Code::from(GrpcCode::Unauthenticated), Code::Unauthenticated);
+}
@@ -0,0 +1,105 @@
+use errno::{errno, set_errno, Errno};
+use libc;
+use std::io::ErrorKind;
+
+use BsdError;
+
+impl BsdError {
+    /// Set errno of current thread, e.g. to report failure from a C callback
+    /// implemented in Rust. Value is translated to host numbering, see
+    /// to_host_raw(), so from_errno() gives the same error back. Errors the
+    /// host doesn't have are replaced with the closest one by io_kind(), e.g.
+    /// ECAPMODE becomes EPERM on Linux. So are Unknown ones, unless the host
+    /// is FreeBSD: their code means something else on other systems.
+    pub fn set_errno(self) {
+        let code = self.to_host_raw().unwrap_or_else(|| match self.io_kind() {
+            ErrorKind::PermissionDenied => libc::EPERM,
+            ErrorKind::WouldBlock => libc::EAGAIN,
+            ErrorKind::InvalidInput => libc::EINVAL,
+            _ => libc::ENOTSUP,
+        });
+        set_errno(Errno(code));
+    }
+
+    /// Set errno of current thread to 0.
+    pub fn clear_errno() {
+        set_errno(Errno(0));
+    }
+}
+
+/// Saves errno of current thread and puts it back when dropped. Useful in
+/// signal handlers and destructors that call libc, but must not clobber
+/// errno of the code they interrupted.
+///
+/// ```
+/// # use blunder::{BsdError, ErrnoGuard};
+/// BsdError::ENOENT.set_errno();
+/// {
+///     let _guard = ErrnoGuard::new();
+///     BsdError::EIO.set_errno();
+/// }
+/// assert_eq!(BsdError::from_errno(), Some(BsdError::ENOENT));
+/// ```
+#[derive(Debug)]
+pub struct ErrnoGuard {
+    saved: Errno,
+}
+
+impl ErrnoGuard {
+    pub fn new() -> ErrnoGuard {
+        ErrnoGuard { saved: errno() }
+    }
+
+    /// errno at the time guard was created.
+    pub fn saved(&self) -> i32 {
+        self.saved.0
+    }
+}
+
+impl Default for ErrnoGuard {
+    fn default() -> ErrnoGuard {
+        ErrnoGuard::new()
+    }
+}
+
+impl Drop for ErrnoGuard {
+    fn drop(&mut self) {
+        set_errno(self.saved);
+    }
+}
+
+#[test]
+fn errno_guard() {
+    use std::thread;
+
+    BsdError::EPERM.set_errno();
+    {
+        let guard = ErrnoGuard::new();
+        assert_eq!(BsdError::EPERM.to_host_raw(), Some(guard.saved()));
+        BsdError::clear_errno();
+        assert_eq!(BsdError::from_errno(), None);
+    }
+    assert_eq!(BsdError::from_errno(), Some(BsdError::EPERM));
+
+    // errno is per thread
+    thread::spawn(|| assert_eq!(BsdError::from_errno(), None)).join().unwrap();
+
+    // EAGAIN is 35 on FreeBSD, but 11 on Linux
+    BsdError::EAGAIN.set_errno();
+    assert_eq!(errno().0, libc::EAGAIN);
+    assert_eq!(BsdError::from_errno(), Some(BsdError::EAGAIN));
+
+    BsdError::ECAPMODE.set_errno();
+    #[cfg(target_os = "freebsd")]
+    assert_eq!(BsdError::from_errno(), Some(BsdError::ECAPMODE));
+    #[cfg(not(target_os = "freebsd"))]
+    assert_eq!(errno().0, libc::EPERM);
+
+    // 98 is EADDRINUSE on Linux, unknown FreeBSD code must not turn into it
+    BsdError::from_raw(98).unwrap().set_errno();
+    #[cfg(target_os = "freebsd")]
+    assert_eq!(errno().0, 98);
+    #[cfg(not(target_os = "freebsd"))]
+    assert_eq!(errno().0, libc::ENOTSUP);
+    BsdError::clear_errno();
+}
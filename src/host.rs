@@ -0,0 +1,129 @@
+use libc;
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use Os;
+
+#[cfg(target_os = "freebsd")]
+pub use bsd::BsdError as HostError;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub use linux::LinuxError as HostError;
+
+/// Defines portable constants on Errno, values come from libc for the target.
+macro_rules! portable_errno {
+    ($($name:ident,)*) => {
+        impl Errno {
+            $(pub const $name: Errno = Errno(libc::$name);)*
+        }
+    }
+}
+
+/// Error code of the host OS. Unlike BsdError and LinuxError, numbering is
+/// always correct for the platform crate is compiled for, so portable code
+/// should use this one.
+///
+/// ```
+/// # use blunder::Errno;
+/// let err = std::fs::File::open("/does/not/exist").unwrap_err();
+/// assert_eq!(Errno::from_io(&err), Some(Errno::ENOENT));
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Errno(i32);
+
+portable_errno! {
+    EPERM, ENOENT, ESRCH, EINTR, EIO, ENXIO, E2BIG, ENOEXEC, EBADF, ECHILD, EAGAIN, ENOMEM,
+    EACCES, EFAULT, ENOTBLK, EBUSY, EEXIST, EXDEV, ENODEV, ENOTDIR, EISDIR, EINVAL, ENFILE,
+    EMFILE, ENOTTY, ETXTBSY, EFBIG, ENOSPC, ESPIPE, EROFS, EMLINK, EPIPE, EDOM, ERANGE, EDEADLK,
+    ENAMETOOLONG, ENOLCK, ENOSYS, ENOTEMPTY, ELOOP, ENOMSG, EIDRM, EREMOTE, EUSERS, EADDRINUSE,
+    EADDRNOTAVAIL, EAFNOSUPPORT, EALREADY, ECONNABORTED, ECONNREFUSED, ECONNRESET, EDESTADDRREQ,
+    EHOSTDOWN, EHOSTUNREACH, EINPROGRESS, EISCONN, EMSGSIZE, ENETDOWN, ENETRESET, ENETUNREACH,
+    ENOBUFS, ENOPROTOOPT, ENOTCONN, ENOTSOCK, EOPNOTSUPP, EPFNOSUPPORT, EPROTO, EPROTONOSUPPORT,
+    EPROTOTYPE, ESHUTDOWN, ESOCKTNOSUPPORT, ETIMEDOUT, ETOOMANYREFS, EOVERFLOW, ECANCELED, EILSEQ,
+    EBADMSG, EDQUOT, ESTALE, EMULTIHOP, ENOLINK, EOWNERDEAD, ENOTRECOVERABLE,
+}
+
+impl Errno {
+    /// Create Errno from errno in current thread. Returns None only if there
+    /// is no error at all.
+    pub fn from_errno() -> Option<Errno> {
+        Errno::from_raw(::errno::errno().0)
+    }
+
+    /// Returns None only for 0.
+    pub fn from_raw(code: i32) -> Option<Errno> {
+        if code == 0 {
+            None
+        } else {
+            Some(Errno(code))
+        }
+    }
+
+    /// Errno behind io::Error, if there is one.
+    pub fn from_io(err: &io::Error) -> Option<Errno> {
+        err.raw_os_error().and_then(Errno::from_raw)
+    }
+
+    pub fn raw_os_error(&self) -> i32 {
+        self.0
+    }
+
+    /// Same error in terms of host OS table, i.e. BsdError on FreeBSD and
+    /// LinuxError on Linux.
+    #[cfg(any(target_os = "freebsd", target_os = "linux", target_os = "android"))]
+    pub fn to_host(&self) -> HostError {
+        HostError::from_raw(self.0).unwrap_or(HostError::Unknown(self.0))
+    }
+
+    /// Symbolic name, e.g. "ENOENT". "UNKNOWN" when numbering of the host OS
+    /// is not known or the code is not in it.
+    pub fn name(&self) -> &'static str {
+        Os::host().and_then(|os| os.name_of(self.0)).unwrap_or("UNKNOWN")
+    }
+
+    /// strerror(3) of the host libc.
+    pub fn message(&self) -> String {
+        let msg = io::Error::from_raw_os_error(self.0).to_string();
+        // Strip " (os error N)" that std appends.
+        match msg.rfind(" (os error ") {
+            Some(idx) => msg[..idx].to_owned(),
+            None => msg,
+        }
+    }
+}
+
+impl From<Errno> for io::Error {
+    fn from(err: Errno) -> io::Error {
+        io::Error::from_raw_os_error(err.0)
+    }
+}
+
+impl Error for Errno {}
+
+/// Prints "ENOENT: No such file or directory".
+impl fmt::Display for Errno {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.name(), self.message())
+    }
+}
+
+#[test]
+fn errno() {
+    let err = ::std::fs::File::open("/does/not/exist").unwrap_err();
+    let errno = Errno::from_io(&err).unwrap();
+    assert_eq!(errno, Errno::ENOENT);
+    assert_ne!(errno, Errno::EACCES);
+    assert_eq!(Errno::from_raw(0), None);
+    assert_eq!(io::Error::from(Errno::EACCES).kind(), io::ErrorKind::PermissionDenied);
+
+    #[cfg(target_os = "linux")]
+    {
+        use LinuxError;
+
+        assert_eq!(Errno::EAGAIN.to_host(), LinuxError::EAGAIN);
+        assert_eq!(Errno::EDEADLK.raw_os_error(), LinuxError::EDEADLK.raw_os_error());
+        assert_eq!(errno.to_string(), "ENOENT: No such file or directory");
+        assert_eq!(Errno::EHOSTDOWN.name(), "EHOSTDOWN");
+        assert_eq!(Errno(4096).name(), "UNKNOWN");
+    }
+}
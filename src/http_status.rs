@@ -0,0 +1,59 @@
+#[cfg(feature = "http")]
+use http::StatusCode;
+use std::error::Error as StdError;
+
+use Blunder;
+use BsdError;
+
+/// Error kind that maps to an HTTP status code, so gateways don't need a
+/// match of their own.
+pub trait HttpStatus {
+    fn http_status(&self) -> u16;
+
+    /// Same as http_status(), as http::StatusCode. Invalid codes become 500.
+    #[cfg(feature = "http")]
+    fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.http_status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+}
+
+/// Errors that have an obvious HTTP counterpart get it, everything else is
+/// 500.
+impl HttpStatus for BsdError {
+    fn http_status(&self) -> u16 {
+        match *self {
+            BsdError::EINVAL => 400,
+            BsdError::EACCES | BsdError::EPERM => 403,
+            BsdError::ENOENT => 404,
+            BsdError::EEXIST => 409,
+            BsdError::EAGAIN => 503,
+            BsdError::ETIMEDOUT => 504,
+            BsdError::ENOSPC | BsdError::EDQUOT => 507,
+            _ => 500,
+        }
+    }
+}
+
+/// Status of the kind.
+impl<T: HttpStatus + StdError + Clone> HttpStatus for Blunder<T> {
+    fn http_status(&self) -> u16 {
+        self.kind.http_status()
+    }
+}
+
+#[test]
+fn http_status() {
+    assert_eq!(BsdError::ENOENT.http_status(), 404);
+    assert_eq!(BsdError::EPERM.http_status(), 403);
+    assert_eq!(BsdError::EDQUOT.http_status(), 507);
+    assert_eq!(BsdError::EIO.http_status(), 500);
+    assert_eq!(BsdError::Unknown(1000).http_status(), 500);
+    assert_eq!(Blunder::new(BsdError::EEXIST).http_status(), 409);
+    assert_eq!(Blunder::new(BsdError::EAGAIN).problem_details().status(), 503);
+
+    #[cfg(feature = "http")]
+    {
+        assert_eq!(BsdError::ETIMEDOUT.status_code(), StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(Blunder::new(BsdError::EINVAL).status_code(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,134 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use BsdError;
+
+impl BsdError {
+    /// Closest io::ErrorKind, mostly the same std picks for this errno.
+    pub fn io_kind(&self) -> io::ErrorKind {
+        use std::io::ErrorKind::*;
+
+        match *self {
+            BsdError::EPERM | BsdError::EACCES => PermissionDenied,
+            BsdError::ENOTCAPABLE | BsdError::ECAPMODE => PermissionDenied,
+            BsdError::ENOENT => NotFound,
+            BsdError::EINTR => Interrupted,
+            BsdError::E2BIG => ArgumentListTooLong,
+            BsdError::EDEADLK => Deadlock,
+            BsdError::ENOMEM => OutOfMemory,
+            BsdError::EBUSY => ResourceBusy,
+            BsdError::EEXIST => AlreadyExists,
+            BsdError::EXDEV => CrossesDevices,
+            BsdError::ENOTDIR => NotADirectory,
+            BsdError::EISDIR => IsADirectory,
+            BsdError::EINVAL => InvalidInput,
+            BsdError::ETXTBSY => ExecutableFileBusy,
+            BsdError::EFBIG => FileTooLarge,
+            BsdError::ENOSPC => StorageFull,
+            BsdError::ESPIPE => NotSeekable,
+            BsdError::EROFS => ReadOnlyFilesystem,
+            BsdError::EMLINK => TooManyLinks,
+            BsdError::EPIPE => BrokenPipe,
+            BsdError::EAGAIN => WouldBlock,
+            BsdError::EADDRINUSE => AddrInUse,
+            BsdError::EADDRNOTAVAIL => AddrNotAvailable,
+            BsdError::ENETDOWN => NetworkDown,
+            BsdError::ENETUNREACH => NetworkUnreachable,
+            BsdError::ECONNABORTED => ConnectionAborted,
+            BsdError::ECONNRESET => ConnectionReset,
+            BsdError::ENOTCONN => NotConnected,
+            BsdError::ETIMEDOUT => TimedOut,
+            BsdError::ECONNREFUSED => ConnectionRefused,
+            BsdError::ENAMETOOLONG => InvalidFilename,
+            BsdError::EHOSTUNREACH => HostUnreachable,
+            BsdError::ENOTEMPTY => DirectoryNotEmpty,
+            BsdError::EDQUOT => QuotaExceeded,
+            BsdError::ESTALE => StaleNetworkFileHandle,
+            BsdError::ENOSYS | BsdError::EOPNOTSUPP => Unsupported,
+            _ => Other,
+        }
+    }
+}
+
+/// Code is translated to host numbering, see BsdError::to_host_raw(). Errors
+/// that don't exist on the host are wrapped as custom io::Error with
+/// io_kind().
+impl From<BsdError> for io::Error {
+    fn from(err: BsdError) -> io::Error {
+        match err.to_host_raw() {
+            Some(code) => io::Error::from_raw_os_error(code),
+            None => io::Error::new(err.io_kind(), err),
+        }
+    }
+}
+
+/// Returned when there is no errno behind io::Error, e.g. one made with
+/// io::Error::other().
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct NoErrnoError {
+    kind: io::ErrorKind,
+}
+
+impl NoErrnoError {
+    /// Kind of io::Error that failed to convert.
+    pub fn kind(&self) -> io::ErrorKind {
+        self.kind
+    }
+}
+
+impl Error for NoErrnoError {}
+
+impl fmt::Display for NoErrnoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "no errno behind io::Error of kind {:?}", self.kind)
+    }
+}
+
+/// Reverse of `From<BsdError> for io::Error`. Fails if there is no errno
+/// behind io::Error. Errors FreeBSD doesn't have come back as Host.
+impl<'a> TryFrom<&'a io::Error> for BsdError {
+    type Error = NoErrnoError;
+
+    fn try_from(err: &'a io::Error) -> Result<BsdError, NoErrnoError> {
+        if let Some(bsd) = err.get_ref().and_then(|inner| inner.downcast_ref::<BsdError>()) {
+            return Ok(*bsd);
+        }
+        err.raw_os_error()
+            .and_then(BsdError::from_host_raw)
+            .ok_or(NoErrnoError { kind: err.kind() })
+    }
+}
+
+#[test]
+fn io_error() {
+    let err = ::std::fs::File::open("/does/not/exist").unwrap_err();
+    assert_eq!(BsdError::try_from(&err), Ok(BsdError::ENOENT));
+
+    let io = io::Error::from(BsdError::ECONNREFUSED);
+    assert_eq!(io.kind(), io::ErrorKind::ConnectionRefused);
+    assert_eq!(BsdError::try_from(&io), Ok(BsdError::ECONNREFUSED));
+
+    let io = io::Error::from(BsdError::ECAPMODE);
+    assert_eq!(io.kind(), io::ErrorKind::PermissionDenied);
+    assert_eq!(BsdError::try_from(&io), Ok(BsdError::ECAPMODE));
+
+    let err = BsdError::try_from(&io::Error::other("nope")).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+    assert_eq!(err.to_string(), "no errno behind io::Error of kind Other");
+    assert_eq!(BsdError::ENOENT.io_kind(), io::ErrorKind::NotFound);
+    assert_eq!(BsdError::EDOOFUS.io_kind(), io::ErrorKind::Other);
+
+    // ECHRNG is Linux only, it must come back the same, not as FreeBSD 44
+    #[cfg(target_os = "linux")]
+    {
+        ::errno::set_errno(::errno::Errno(libc::ECHRNG));
+        let err = ::cvt(-1).unwrap_err();
+        assert_eq!(err.kind(), BsdError::Host(libc::ECHRNG));
+        let io = io::Error::from(err.kind());
+        assert_eq!(io.raw_os_error(), Some(libc::ECHRNG));
+        assert_eq!(BsdError::try_from(&io), Ok(BsdError::Host(libc::ECHRNG)));
+        ::errno::set_errno(::errno::Errno(0));
+    }
+}
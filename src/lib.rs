@@ -1,24 +1,86 @@
 #![doc(html_root_url = "https://andoriyu.github.io/blunder.rs/")]
-#[macro_use]
-extern crate enum_primitive;
 extern crate num;
 
 extern crate errno;
+extern crate libc;
+#[cfg(feature = "http")]
+extern crate http;
+#[cfg(feature = "tonic")]
+extern crate tonic;
+#[cfg(feature = "tonic")]
+extern crate tonic_types;
+#[cfg(feature = "serde")]
+extern crate serde;
 
+#[cfg(feature = "backtrace")]
+use std::backtrace::Backtrace;
 use std::convert::From;
 use std::error::Error as StdError;
 use std::fmt;
 use std::ops::Deref;
 
 
+#[macro_use]
+mod macros;
+
 mod bsd;
+mod category;
+mod cvt;
+mod exit;
+mod ext;
+#[cfg(unix)]
+mod gai;
+mod grpc;
+mod guard;
+#[cfg(unix)]
+mod host;
+mod http_status;
+mod io_error;
+mod linux;
+mod location;
+mod parse;
+mod problem;
+mod report;
+pub mod retry;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod translate;
 
 pub use bsd::*;
+pub use category::*;
+pub use cvt::*;
+pub use exit::*;
+pub use ext::*;
+#[cfg(unix)]
+pub use gai::*;
+pub use grpc::*;
+pub use guard::*;
+#[cfg(unix)]
+pub use host::*;
+pub use http_status::*;
+pub use io_error::*;
+pub use linux::*;
+pub use location::*;
+pub use parse::*;
+pub use problem::*;
+pub use report::*;
+pub use translate::*;
 
+/// Return early with given error converted into error type of the function.
+/// Blunder made from a kind remembers where it was thrown from.
+///
+/// `fail!(located kind)` is for functions returning Blunder: location is set
+/// right here and also has module path in it.
 #[macro_export]
 macro_rules! fail {
+    (located $expr:expr) => (
+        return ::std::result::Result::Err($crate::Blunder::located_at(
+            ::std::convert::From::from($expr),
+            $crate::Location::new(file!(), line!(), column!(), Some(module_path!())),
+        ))
+        );
     ($expr:expr) => (
-        return ::std::result::Result::Err(::std::convert::From::from($expr));
+        return ::std::result::Result::Err(::std::convert::From::from($expr))
         )
 }
 
@@ -33,83 +95,255 @@ macro_rules! maybe_fail {
     })
 }
 
+/// Same as maybe_fail!, but for APIs that return error number directly
+/// instead of setting errno (pthread_*, posix_spawn and friends). See
+/// BsdError::check_rc().
+#[macro_export]
+macro_rules! maybe_fail_rc {
+    ($expr:expr) => ({
+        if let ::std::result::Result::Err(err) = $crate::BsdError::check_rc($expr) {
+            $crate::fail!(err)
+        }
+    })
+}
+
 /// Generic af struct for errror handling
 /// Designed to host anything that implements error::Error trait
 /// Yet can host whatever (like errno from libc)
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub struct Blunder<T: StdError + Clone> {
     /// How to identify the error
     kind: T,
     detail: Option<String>,
+    /// Underlying error that lead to this one, if any
+    source: Option<Box<dyn StdError + Send + Sync>>,
+    /// Where it all went wrong
+    location: Option<Location>,
+    /// Boxed to keep Result<_, Blunder> small
+    #[cfg(feature = "backtrace")]
+    backtrace: Box<Backtrace>,
+}
+
+/// Sources are compared by their Display output, since there is no way to
+/// compare two trait objects. Location is not compared at all.
+impl<T: StdError + Clone + PartialEq> PartialEq for Blunder<T> {
+    fn eq(&self, other: &Blunder<T>) -> bool {
+        self.kind == other.kind && self.detail == other.detail &&
+            self.source.as_ref().map(|e| e.to_string()) ==
+                other.source.as_ref().map(|e| e.to_string())
+    }
 }
 
 /// Because we want easy switch/case on kind...
 impl<T: StdError + Copy + Clone> Deref for Blunder<T> {
     type Target = T;
 
-    fn deref<'a>(&'a self) -> &'a T {
+    fn deref(&self) -> &T {
         &self.kind
     }
 }
 impl<T: StdError + Clone> Blunder<T> {
+    /// Create Blunder of given kind without any detail. Location of the
+    /// caller is recorded.
+    #[track_caller]
+    pub fn new(kind: T) -> Blunder<T> {
+        Blunder {
+            kind,
+            detail: None,
+            source: None,
+            location: Some(Location::caller()),
+            #[cfg(feature = "backtrace")]
+            backtrace: Box::new(Backtrace::capture()),
+        }
+    }
+
+    /// Create Blunder of given kind with a detail message attached.
+    #[track_caller]
+    pub fn with_detail<S: Into<String>>(kind: T, detail: S) -> Blunder<T> {
+        let mut blunder = Blunder::new(kind);
+        blunder.set_detail(detail);
+        blunder
+    }
+
+    /// Create Blunder of given kind that was caused by another error. Think
+    /// application error caused by BsdError caused by io::Error.
+    #[track_caller]
+    pub fn caused_by<E>(kind: T, source: E) -> Blunder<T>
+    where
+        E: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        let mut blunder = Blunder::new(kind);
+        blunder.set_source(source);
+        blunder
+    }
+
+    /// Start building Blunder of given kind. See BlunderBuilder.
+    #[track_caller]
+    pub fn builder(kind: T) -> BlunderBuilder<T> {
+        BlunderBuilder { inner: Blunder::new(kind) }
+    }
+
     /// Optional reasoning behind such behavior.
     /// Think "Client doesn't understand XXX cipher"
     pub fn detail(&self) -> Option<String> {
         self.detail.clone()
     }
 
+    /// Replace detail message with a new one.
+    pub fn set_detail<S: Into<String>>(&mut self, detail: S) {
+        self.detail = Some(detail.into());
+    }
+
+    /// Transform detail message, e.g. to prepend more context to it.
+    /// Returning None drops the detail.
+    pub fn map_detail<F>(mut self, f: F) -> Blunder<T>
+    where
+        F: FnOnce(Option<String>) -> Option<String>,
+    {
+        self.detail = f(self.detail.take());
+        self
+    }
+
+    /// Replace underlying cause of this error.
+    pub fn set_source<E>(&mut self, source: E)
+    where
+        E: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        self.source = Some(source.into());
+    }
+
+    /// Take underlying cause out, e.g. to downcast it.
+    pub fn take_source(&mut self) -> Option<Box<dyn StdError + Send + Sync>> {
+        self.source.take()
+    }
+
+    /// Where this error was created or thrown with fail!.
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+
+    pub fn set_location(&mut self, location: Location) {
+        self.location = Some(location);
+    }
+
+    /// Same as set_location(), but chainable. Used by `fail!(located ...)`.
+    pub fn located_at(mut self, location: Location) -> Blunder<T> {
+        self.set_location(location);
+        self
+    }
+
+    /// Stack trace captured when this Blunder was created. Whether it's
+    /// actually captured depends on RUST_BACKTRACE and RUST_LIB_BACKTRACE.
+    #[cfg(feature = "backtrace")]
+    pub fn backtrace(&self) -> &Backtrace {
+        &self.backtrace
+    }
+
+    /// Render full multi-line diagnostic: kind, description, detail, location
+    /// and chain of sources.
+    pub fn report(&self) -> Report<'_, T> {
+        Report::new(self)
+    }
+
     pub fn kind(&self) -> T {
         self.kind.clone()
     }
 }
+
+/// Fluent way to put Blunder together.
+///
+/// ```
+/// # use blunder::{Blunder, BsdError};
+/// let err = Blunder::builder(BsdError::EPROTO)
+///     .detail("client doesn't understand XXX cipher")
+///     .build();
+/// assert_eq!(err.detail(), Some("client doesn't understand XXX cipher".to_owned()));
+/// ```
+#[derive(Debug)]
+pub struct BlunderBuilder<T: StdError + Clone> {
+    inner: Blunder<T>,
+}
+
+impl<T: StdError + Clone> BlunderBuilder<T> {
+    /// Attach detail message.
+    pub fn detail<S: Into<String>>(mut self, detail: S) -> BlunderBuilder<T> {
+        self.inner.set_detail(detail);
+        self
+    }
+
+    /// Attach underlying cause.
+    pub fn source<E>(mut self, source: E) -> BlunderBuilder<T>
+    where
+        E: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        self.inner.set_source(source);
+        self
+    }
+
+    /// Override location recorded by Blunder::builder().
+    pub fn location(mut self, location: Location) -> BlunderBuilder<T> {
+        self.inner.set_location(location);
+        self
+    }
+
+    pub fn build(self) -> Blunder<T> {
+        self.inner
+    }
+}
+
+#[allow(deprecated)]
 impl<T: StdError + Clone> StdError for Blunder<T> {
     fn description(&self) -> &str {
         self.kind.description()
     }
-    fn cause(&self) -> Option<&StdError> {
-        self.kind.cause()
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self.source {
+            Some(ref source) => Some(&**source),
+            None => self.kind.source(),
+        }
     }
 }
 
 impl<T: StdError + Clone> fmt::Display for Blunder<T> {
+    #[allow(deprecated)]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.description())
+        write!(f, "{}", self.description())?;
+        if let Some(ref location) = self.location {
+            write!(f, " at {}", location)?;
+        }
+        Ok(())
     }
 }
 impl<E: StdError + Clone> From<E> for Blunder<E> {
+    #[track_caller]
     fn from(err: E) -> Blunder<E> {
-        Blunder {
-            kind: err,
-            detail: None,
-        }
+        Blunder::new(err)
     }
 }
 
 #[test]
+#[allow(deprecated)]
 fn it_works() {
     #[derive(Debug, PartialEq, Clone)]
     enum Wat {
         One,
-    };
+    }
     impl fmt::Display for Wat {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            write!(f, "{}", "wat")
+            write!(f, "wat")
         }
     }
     impl StdError for Wat {
         fn description(&self) -> &str {
             "wat"
         }
-        fn cause(&self) -> Option<&StdError> {
+        fn source(&self) -> Option<&(dyn StdError + 'static)> {
             None
         }
     }
 
-    let error: Blunder<Wat> = Blunder {
-        kind: Wat::One,
-        detail: None,
-    };
-    assert_eq!(error.cause().is_some(), false);
+    let error: Blunder<Wat> = Blunder::new(Wat::One);
+    assert!(error.source().is_none());
     assert_eq!(error.description(), "wat");
     assert_eq!(error.kind(), Wat::One);
 
@@ -117,15 +351,125 @@ fn it_works() {
 
     fn goto_fail() -> Result<(), Blunder<Wat>> {
         fail!(Wat::One)
-    };
+    }
+
+    let line = line!() - 3;
 
-    let fail = Blunder {
-        kind: Wat::One,
-        detail: None,
-    };
+    let fail = Blunder::new(Wat::One);
     if let Err(err) = goto_fail() {
         assert_eq!(err, fail);
+        let location = err.location().unwrap();
+        assert_eq!(location.file(), file!());
+        assert_eq!(location.line(), line);
     } else {
         panic!();
     }
+
+    fn pass_through(err: Blunder<Wat>) -> Result<(), Blunder<Wat>> {
+        fail!(err)
+    }
+
+    let location = fail.location().cloned();
+    let err = pass_through(fail).unwrap_err();
+    assert_eq!(err.location(), location.as_ref());
+
+    fn open() -> ::std::io::Result<()> {
+        fail!(::std::io::Error::other("disk on fire"))
+    }
+
+    assert_eq!(open().unwrap_err().to_string(), "disk on fire");
+
+    fn goto_fail_located() -> Result<(), Blunder<Wat>> {
+        fail!(located Wat::One)
+    }
+
+    let err = goto_fail_located().unwrap_err();
+    let location = err.location().unwrap();
+    assert_eq!(location.line(), line!() - 5);
+    assert_eq!(location.module_path(), Some(module_path!()));
+}
+
+#[test]
+fn detail() {
+    let mut err = Blunder::with_detail(BsdError::EPROTO, "no XXX cipher");
+    assert_eq!(err.detail(), Some("no XXX cipher".to_owned()));
+
+    err.set_detail("no YYY cipher");
+    assert_eq!(err.detail(), Some("no YYY cipher".to_owned()));
+
+    let err = err.map_detail(|d| d.map(|d| format!("handshake: {}", d)));
+    assert_eq!(err.detail(), Some("handshake: no YYY cipher".to_owned()));
+    assert_eq!(err.kind(), BsdError::EPROTO);
+
+    let built = Blunder::builder(BsdError::EPROTO)
+        .detail("handshake: no YYY cipher")
+        .build();
+    assert_eq!(built, err);
+    assert_eq!(Blunder::new(BsdError::EPROTO).detail(), None);
+}
+
+#[test]
+fn source_chain() {
+    use std::io;
+
+    let io = io::Error::other("disk on fire");
+    let bsd = Blunder::caused_by(BsdError::EIO, io);
+    let err = Blunder::builder(BsdError::ENXIO)
+        .detail("can't load config")
+        .source(bsd)
+        .build();
+
+    let source = err.source().unwrap();
+    let bsd = source.downcast_ref::<Blunder<BsdError>>().unwrap();
+    assert_eq!(bsd.kind(), BsdError::EIO);
+    let io = bsd.source().unwrap();
+    assert_eq!(io.to_string(), "disk on fire");
+    assert!(io.source().is_none());
+}
+
+#[test]
+fn location() {
+    fn open() -> Result<(), Blunder<BsdError>> {
+        Err(BsdError::ENOENT)?
+    }
+    let line = line!() - 2;
+
+    let err = open().unwrap_err();
+    let location = err.location().unwrap();
+    assert_eq!(location.file(), file!());
+    assert_eq!(location.line(), line);
+    assert_eq!(location.module_path(), None);
+    assert!(err.to_string().ends_with(&format!(" at {}:{}:9", file!(), line)));
+}
+
+#[cfg(feature = "backtrace")]
+#[test]
+fn backtrace() {
+    use std::backtrace::BacktraceStatus;
+
+    let err = Blunder::new(BsdError::EDOOFUS);
+    if err.backtrace().status() == BacktraceStatus::Captured {
+        assert!(err.backtrace().to_string().contains("blunder::backtrace"));
+    }
+}
+
+#[test]
+fn maybe_fail_rc() {
+    use libc::c_int;
+    use std::mem;
+
+    fn settype(kind: c_int) -> Result<(), Blunder<BsdError>> {
+        unsafe {
+            let mut attr: libc::pthread_mutexattr_t = mem::zeroed();
+            maybe_fail_rc!(libc::pthread_mutexattr_init(&mut attr));
+            let rc = libc::pthread_mutexattr_settype(&mut attr, kind);
+            libc::pthread_mutexattr_destroy(&mut attr);
+            maybe_fail_rc!(rc);
+        }
+        Ok(())
+    }
+    assert!(settype(libc::PTHREAD_MUTEX_RECURSIVE).is_ok());
+    let err = settype(9999).unwrap_err();
+    assert_eq!(err.kind(), BsdError::EINVAL);
+    assert_eq!(err.location().unwrap().file(), file!());
 }
@@ -0,0 +1,346 @@
+errno_enum! {
+    /// Errors that can be encoutered while working with Linux libc. Numbering
+    /// follows asm-generic/errno.h, which is used by most architectures.
+    pub enum LinuxError {
+        EPERM = 1 => "Operation not permitted",
+        ENOENT = 2 => "No such file or directory",
+        ESRCH = 3 => "No such process",
+        EINTR = 4 => "Interrupted system call",
+        EIO = 5 => "Input/output error",
+        ENXIO = 6 => "No such device or address",
+        E2BIG = 7 => "Argument list too long",
+        ENOEXEC = 8 => "Exec format error",
+        EBADF = 9 => "Bad file descriptor",
+        ECHILD = 10 => "No child processes",
+        EAGAIN = 11 => "Resource temporarily unavailable",
+        ENOMEM = 12 => "Cannot allocate memory",
+        EACCES = 13 => "Permission denied",
+        EFAULT = 14 => "Bad address",
+        ENOTBLK = 15 => "Block device required",
+        EBUSY = 16 => "Device or resource busy",
+        EEXIST = 17 => "File exists",
+        EXDEV = 18 => "Invalid cross-device link",
+        ENODEV = 19 => "No such device",
+        ENOTDIR = 20 => "Not a directory",
+        EISDIR = 21 => "Is a directory",
+        EINVAL = 22 => "Invalid argument",
+        ENFILE = 23 => "Too many open files in system",
+        EMFILE = 24 => "Too many open files",
+        ENOTTY = 25 => "Inappropriate ioctl for device",
+        ETXTBSY = 26 => "Text file busy",
+        EFBIG = 27 => "File too large",
+        ENOSPC = 28 => "No space left on device",
+        ESPIPE = 29 => "Illegal seek",
+        EROFS = 30 => "Read-only file system",
+        EMLINK = 31 => "Too many links",
+        EPIPE = 32 => "Broken pipe",
+        EDOM = 33 => "Numerical argument out of domain",
+        ERANGE = 34 => "Numerical result out of range",
+        EDEADLK = 35 => "Resource deadlock avoided",
+        ENAMETOOLONG = 36 => "File name too long",
+        ENOLCK = 37 => "No locks available",
+        ENOSYS = 38 => "Function not implemented",
+        ENOTEMPTY = 39 => "Directory not empty",
+        ELOOP = 40 => "Too many levels of symbolic links",
+        ENOMSG = 42 => "No message of desired type",
+        EIDRM = 43 => "Identifier removed",
+        ECHRNG = 44 => "Channel number out of range",
+        EL2NSYNC = 45 => "Level 2 not synchronized",
+        EL3HLT = 46 => "Level 3 halted",
+        EL3RST = 47 => "Level 3 reset",
+        ELNRNG = 48 => "Link number out of range",
+        EUNATCH = 49 => "Protocol driver not attached",
+        ENOCSI = 50 => "No CSI structure available",
+        EL2HLT = 51 => "Level 2 halted",
+        EBADE = 52 => "Invalid exchange",
+        EBADR = 53 => "Invalid request descriptor",
+        EXFULL = 54 => "Exchange full",
+        ENOANO = 55 => "No anode",
+        EBADRQC = 56 => "Invalid request code",
+        EBADSLT = 57 => "Invalid slot",
+        EBFONT = 59 => "Bad font file format",
+        ENOSTR = 60 => "Device not a stream",
+        ENODATA = 61 => "No data available",
+        ETIME = 62 => "Timer expired",
+        ENOSR = 63 => "Out of streams resources",
+        ENONET = 64 => "Machine is not on the network",
+        ENOPKG = 65 => "Package not installed",
+        EREMOTE = 66 => "Object is remote",
+        ENOLINK = 67 => "Link has been severed",
+        EADV = 68 => "Advertise error",
+        ESRMNT = 69 => "Srmount error",
+        ECOMM = 70 => "Communication error on send",
+        EPROTO = 71 => "Protocol error",
+        EMULTIHOP = 72 => "Multihop attempted",
+        EDOTDOT = 73 => "RFS specific error",
+        EBADMSG = 74 => "Bad message",
+        EOVERFLOW = 75 => "Value too large for defined data type",
+        ENOTUNIQ = 76 => "Name not unique on network",
+        EBADFD = 77 => "File descriptor in bad state",
+        EREMCHG = 78 => "Remote address changed",
+        ELIBACC = 79 => "Can not access a needed shared library",
+        ELIBBAD = 80 => "Accessing a corrupted shared library",
+        ELIBSCN = 81 => ".lib section in a.out corrupted",
+        ELIBMAX = 82 => "Attempting to link in too many shared libraries",
+        ELIBEXEC = 83 => "Cannot exec a shared library directly",
+        EILSEQ = 84 => "Invalid or incomplete multibyte or wide character",
+        ERESTART = 85 => "Interrupted system call should be restarted",
+        ESTRPIPE = 86 => "Streams pipe error",
+        EUSERS = 87 => "Too many users",
+        ENOTSOCK = 88 => "Socket operation on non-socket",
+        EDESTADDRREQ = 89 => "Destination address required",
+        EMSGSIZE = 90 => "Message too long",
+        EPROTOTYPE = 91 => "Protocol wrong type for socket",
+        ENOPROTOOPT = 92 => "Protocol not available",
+        EPROTONOSUPPORT = 93 => "Protocol not supported",
+        ESOCKTNOSUPPORT = 94 => "Socket type not supported",
+        EOPNOTSUPP = 95 => "Operation not supported",
+        EPFNOSUPPORT = 96 => "Protocol family not supported",
+        EAFNOSUPPORT = 97 => "Address family not supported by protocol",
+        EADDRINUSE = 98 => "Address already in use",
+        EADDRNOTAVAIL = 99 => "Cannot assign requested address",
+        ENETDOWN = 100 => "Network is down",
+        ENETUNREACH = 101 => "Network is unreachable",
+        ENETRESET = 102 => "Network dropped connection on reset",
+        ECONNABORTED = 103 => "Software caused connection abort",
+        ECONNRESET = 104 => "Connection reset by peer",
+        ENOBUFS = 105 => "No buffer space available",
+        EISCONN = 106 => "Transport endpoint is already connected",
+        ENOTCONN = 107 => "Transport endpoint is not connected",
+        ESHUTDOWN = 108 => "Cannot send after transport endpoint shutdown",
+        ETOOMANYREFS = 109 => "Too many references: cannot splice",
+        ETIMEDOUT = 110 => "Connection timed out",
+        ECONNREFUSED = 111 => "Connection refused",
+        EHOSTDOWN = 112 => "Host is down",
+        EHOSTUNREACH = 113 => "No route to host",
+        EALREADY = 114 => "Operation already in progress",
+        EINPROGRESS = 115 => "Operation now in progress",
+        ESTALE = 116 => "Stale file handle",
+        EUCLEAN = 117 => "Structure needs cleaning",
+        ENOTNAM = 118 => "Not a XENIX named type file",
+        ENAVAIL = 119 => "No XENIX semaphores available",
+        EISNAM = 120 => "Is a named type file",
+        EREMOTEIO = 121 => "Remote I/O error",
+        EDQUOT = 122 => "Disk quota exceeded",
+        ENOMEDIUM = 123 => "No medium found",
+        EMEDIUMTYPE = 124 => "Wrong medium type",
+        ECANCELED = 125 => "Operation canceled",
+        ENOKEY = 126 => "Required key not available",
+        EKEYEXPIRED = 127 => "Key has expired",
+        EKEYREVOKED = 128 => "Key has been revoked",
+        EKEYREJECTED = 129 => "Key was rejected by service",
+        EOWNERDEAD = 130 => "Owner died",
+        ENOTRECOVERABLE = 131 => "State not recoverable",
+        ERFKILL = 132 => "Operation not possible due to RF-kill",
+        EHWPOISON = 133 => "Memory page has hardware error",
+    }
+}
+
+impl LinuxError {
+    /// Full explanation of the error, mostly as found in errno(3) and glibc
+    /// manual. Obscure codes only have their short message.
+    pub fn long_description(&self) -> &'static str {
+        match *self {
+            LinuxError::EPERM => {
+                "Operation not permitted. Only the owner of the file (or other resource) or \
+                 processes with special privileges can perform the operation."
+            }
+            LinuxError::ENOENT => {
+                "No such file or directory. A file that was expected to exist, or a component of \
+                 a pathname, does not exist."
+            }
+            LinuxError::ESRCH => "No such process. No process matches the specified process ID.",
+            LinuxError::EINTR => {
+                "Interrupted system call. An asynchronous signal occurred and prevented completion \
+                 of the call. When this happens, you should try the call again."
+            }
+            LinuxError::EIO => "Input/output error. Usually used for physical read or write errors.",
+            LinuxError::ENXIO => {
+                "No such device or address. The system tried to use the device represented by a \
+                 file you specified, and it couldn't find the device."
+            }
+            LinuxError::E2BIG => {
+                "Argument list too long. Used when the arguments passed to a new program being \
+                 executed with one of the exec functions occupy too much memory space."
+            }
+            LinuxError::ENOEXEC => {
+                "Exec format error. Invalid executable file format. This condition is detected by \
+                 the exec functions."
+            }
+            LinuxError::EBADF => {
+                "Bad file descriptor. For example, I/O on a descriptor that has been closed or \
+                 reading from a descriptor open only for writing (or vice versa)."
+            }
+            LinuxError::ECHILD => {
+                "No child processes. This error happens on operations that are supposed to \
+                 manipulate child processes, when there aren't any processes to manipulate."
+            }
+            LinuxError::EAGAIN => {
+                "Resource temporarily unavailable. The call might work if you try again later. \
+                 On Linux EWOULDBLOCK has the same value."
+            }
+            LinuxError::ENOMEM => {
+                "Cannot allocate memory. The system cannot allocate more virtual memory because \
+                 its capacity is full."
+            }
+            LinuxError::EACCES => {
+                "Permission denied. The file permissions do not allow the attempted operation."
+            }
+            LinuxError::EFAULT => {
+                "Bad address. An invalid pointer was detected. On Linux this is reported instead \
+                 of delivering SIGSEGV."
+            }
+            LinuxError::EBUSY => {
+                "Device or resource busy. A system resource that can't be shared is already in \
+                 use."
+            }
+            LinuxError::EEXIST => {
+                "File exists. An existing file was specified in a context where it only makes \
+                 sense to specify a new file."
+            }
+            LinuxError::EXDEV => {
+                "Invalid cross-device link. An attempt to make an improper link across file \
+                 systems was detected."
+            }
+            LinuxError::ENOTDIR => {
+                "Not a directory. A file that isn't a directory was specified when a directory is \
+                 required."
+            }
+            LinuxError::EISDIR => {
+                "Is a directory. You cannot open a directory for writing, or create or remove \
+                 hard links to it."
+            }
+            LinuxError::EINVAL => {
+                "Invalid argument. This is used to indicate various kinds of problems with \
+                 passing the wrong argument to a library function."
+            }
+            LinuxError::ENFILE => {
+                "Too many open files in system. There are too many distinct file openings in the \
+                 entire system."
+            }
+            LinuxError::EMFILE => {
+                "Too many open files. The current process has too many files open and can't open \
+                 any more. See RLIMIT_NOFILE."
+            }
+            LinuxError::ENOTTY => {
+                "Inappropriate ioctl for device. The ioctl request is not applicable to the \
+                 object referenced by the file descriptor."
+            }
+            LinuxError::ENOSPC => {
+                "No space left on device. Write operation on a file failed because the disk is \
+                 full."
+            }
+            LinuxError::EPIPE => {
+                "Broken pipe. There is no process reading from the other end of a pipe. SIGPIPE \
+                 is generated as well, so this is only seen if the signal is handled or blocked."
+            }
+            LinuxError::EDEADLK => {
+                "Resource deadlock avoided. Allocating a system resource would have resulted in a \
+                 deadlock situation. On Linux EDEADLOCK has the same value."
+            }
+            LinuxError::ENOSYS => {
+                "Function not implemented. The function is not implemented by the kernel or \
+                 library at all."
+            }
+            LinuxError::ELOOP => {
+                "Too many levels of symbolic links. Too many levels of symbolic links were \
+                 encountered in looking up a file name, or O_NOFOLLOW was given and the last \
+                 component is a symbolic link."
+            }
+            LinuxError::ECHRNG => "Channel number out of range. Used by STREAMS drivers.",
+            LinuxError::ENODATA => {
+                "No data available. Most commonly seen as the extended attribute that does not \
+                 exist (ENOATTR on BSD)."
+            }
+            LinuxError::ENOTUNIQ => {
+                "Name not unique on network. Left over from RFS (Remote File Sharing), rarely \
+                 seen nowadays."
+            }
+            LinuxError::EBADFD => {
+                "File descriptor in bad state. The file descriptor is valid, but the object \
+                 behind it is not in a state suitable for the operation."
+            }
+            LinuxError::ERESTART => {
+                "Interrupted system call should be restarted. Used internally by the kernel and \
+                 never returned to user space in normal circumstances."
+            }
+            LinuxError::ENOTCONN => {
+                "Transport endpoint is not connected. The socket is not connected to anything, \
+                 and the operation requires a connected socket."
+            }
+            LinuxError::ETIMEDOUT => {
+                "Connection timed out. A socket operation with a specified timeout received no \
+                 response during the timeout period."
+            }
+            LinuxError::ECONNREFUSED => {
+                "Connection refused. A remote host refused to allow the network connection, \
+                 typically because it is not running the requested service."
+            }
+            LinuxError::ESTALE => {
+                "Stale file handle. This indicates an internal confusion in the file system, \
+                 often because a file on an NFS server was deleted while still open."
+            }
+            LinuxError::EUCLEAN => {
+                "Structure needs cleaning. File system found corrupted on-disk structures and \
+                 needs to be checked with fsck(8)."
+            }
+            LinuxError::EDQUOT => "Disk quota exceeded. The user's disk quota was exceeded.",
+            LinuxError::ENOMEDIUM => {
+                "No medium found. The device is a removable one and there is no medium in it."
+            }
+            LinuxError::ENOKEY => {
+                "Required key not available. The key needed for the operation is not in the \
+                 kernel keyring."
+            }
+            LinuxError::EKEYEXPIRED => {
+                "Key has expired. A key in the kernel keyring has expired and can't be used."
+            }
+            LinuxError::EKEYREVOKED => {
+                "Key has been revoked. A key in the kernel keyring has been revoked and can't be \
+                 used."
+            }
+            LinuxError::EKEYREJECTED => {
+                "Key was rejected by service. The key was found, but the service refused to use \
+                 it."
+            }
+            LinuxError::EOWNERDEAD => {
+                "Owner died. The owner of a robust mutex terminated while holding the mutex lock."
+            }
+            LinuxError::ENOTRECOVERABLE => {
+                "State not recoverable. The state protected by a robust mutex is not recoverable."
+            }
+            LinuxError::ERFKILL => {
+                "Operation not possible due to RF-kill. The wireless device is blocked by an \
+                 rfkill switch."
+            }
+            LinuxError::EHWPOISON => {
+                "Memory page has hardware error. The page was marked as poisoned after a \
+                 hardware memory error was detected in it."
+            }
+            LinuxError::Unknown(_) => {
+                "Unknown error. The error code is not known to this library, it may come from a \
+                 newer kernel."
+            }
+            LinuxError::Host(_) => {
+                "Unknown error. The error code comes from the host OS and has no counterpart on \
+                 Linux."
+            }
+            _ => self.short_message(),
+        }
+    }
+}
+
+#[test]
+fn linux_error() {
+    assert_eq!(LinuxError::COUNT, 131);
+    assert_eq!(LinuxError::all().count(), LinuxError::COUNT);
+    assert_eq!((LinuxError::MIN_RAW, LinuxError::MAX_RAW), (1, 133));
+    for err in LinuxError::all() {
+        assert_eq!(err.name(), format!("{:?}", err));
+        assert!(err.long_description().starts_with(err.short_message()), "{:?}", err);
+        assert_eq!(LinuxError::from_raw(err.raw_os_error()), Some(err));
+    }
+    assert_eq!(LinuxError::from_raw(35), Some(LinuxError::EDEADLK));
+    assert_eq!(LinuxError::from_raw(41), Some(LinuxError::Unknown(41)));
+    assert_eq!(LinuxError::EBADFD.to_string(), "EBADFD: File descriptor in bad state");
+}
@@ -0,0 +1,81 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::panic;
+
+/// Place in the source code where Blunder was created. Strings are owned
+/// only when it came from elsewhere, e.g. was deserialized.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Location {
+    file: Cow<'static, str>,
+    line: u32,
+    column: u32,
+    module_path: Option<Cow<'static, str>>,
+}
+
+impl Location {
+    /// Filled by `fail!(located ...)` from file!(), line!(), column!() and
+    /// module_path!().
+    pub fn new(
+        file: &'static str,
+        line: u32,
+        column: u32,
+        module_path: Option<&'static str>,
+    ) -> Location {
+        Location {
+            file: Cow::Borrowed(file),
+            line,
+            column,
+            module_path: module_path.map(Cow::Borrowed),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_owned(
+        file: String,
+        line: u32,
+        column: u32,
+        module_path: Option<String>,
+    ) -> Location {
+        Location {
+            file: Cow::Owned(file),
+            line,
+            column,
+            module_path: module_path.map(Cow::Owned),
+        }
+    }
+
+    /// Location of the caller, see `#[track_caller]`. There is no way to get
+    /// module path this way.
+    #[track_caller]
+    pub fn caller() -> Location {
+        let caller = panic::Location::caller();
+        Location::new(caller.file(), caller.line(), caller.column(), None)
+    }
+
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    pub fn column(&self) -> u32 {
+        self.column
+    }
+
+    pub fn module_path(&self) -> Option<&str> {
+        self.module_path.as_deref()
+    }
+}
+
+/// Looks like "src/main.rs:42:13" or "src/main.rs:42:13 (app::config)".
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)?;
+        if let Some(ref module_path) = self.module_path {
+            write!(f, " ({})", module_path)?;
+        }
+        Ok(())
+    }
+}
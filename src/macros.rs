@@ -0,0 +1,248 @@
+/// Defines errno enum with its numbering and short messages in one place.
+/// Type is expected to provide long_description() on its own, it's used for
+/// Error::description() and alternate Display, and from_host_raw() with
+/// from_marked_raw(), used by from_errno() and FromStr.
+macro_rules! errno_enum {
+    (
+        $(#[$attr:meta])*
+        pub enum $enum:ident {
+            $($name:ident = $code:expr => $short:expr,)*
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+        pub enum $enum {
+            $($name,)*
+            /// Code in numbering of this table that is not known to this crate,
+            /// e.g. from a newer kernel.
+            Unknown(i32),
+            /// Code of the host OS that has no counterpart in this table, e.g.
+            /// ECHRNG on Linux. Only from_host_raw() makes these.
+            Host(i32),
+        }
+
+        impl $enum {
+            /// Name and code of every known error, used for translation.
+            pub(crate) const TABLE: &'static [(&'static str, i32)] = &[
+                $((stringify!($name), $code),)*
+            ];
+
+            const ALL: &'static [$enum] = &[$($enum::$name,)*];
+
+            /// Number of known errors, Unknown is not counted.
+            pub const COUNT: usize = $enum::ALL.len();
+
+            /// Lowest raw value of known errors.
+            pub const MIN_RAW: i32 = $crate::macros::min_code($enum::TABLE);
+
+            /// Highest raw value of known errors.
+            pub const MAX_RAW: i32 = $crate::macros::max_code($enum::TABLE);
+
+            /// Every known error in order of raw value. Unknown is not included.
+            pub fn all() -> impl Iterator<Item = $enum> {
+                $enum::ALL.iter().cloned()
+            }
+
+            /// Create error from errno in current thread, translated from host
+            /// numbering with from_host_raw(). Returns None only if there is
+            /// no error at all.
+            pub fn from_errno() -> Option<$enum> {
+                $enum::from_host_raw(::errno::errno().0)
+            }
+
+            /// Create error from raw error code. Returns None only for 0,
+            /// codes that are not known end up in Unknown.
+            pub fn from_raw(code: i32) -> Option<$enum> {
+                if code == 0 {
+                    None
+                } else {
+                    Some($enum::from_known(code).unwrap_or($enum::Unknown(code)))
+                }
+            }
+
+            /// Only known codes, Unknown is never returned.
+            fn from_known(code: i32) -> Option<$enum> {
+                match code {
+                    $($code => Some($enum::$name),)*
+                    _ => None,
+                }
+            }
+
+            /// Numeric value of the error in numbering of this table, Host
+            /// errors are in host numbering. Use to_host_raw() to hand it back
+            /// to C.
+            pub fn raw_os_error(&self) -> i32 {
+                match *self {
+                    $($enum::$name => $code,)*
+                    $enum::Unknown(code) | $enum::Host(code) => code,
+                }
+            }
+
+            /// Symbolic name of the error, e.g. "ENOENT". Unknown and Host
+            /// errors are named "UNKNOWN".
+            pub fn name(&self) -> &'static str {
+                match *self {
+                    $($enum::$name => stringify!($name),)*
+                    $enum::Unknown(_) | $enum::Host(_) => "UNKNOWN",
+                }
+            }
+
+            /// Short strerror(3)-like message, e.g. "No such file or directory".
+            pub fn short_message(&self) -> &'static str {
+                match *self {
+                    $($enum::$name => $short,)*
+                    $enum::Unknown(_) | $enum::Host(_) => "Unknown error",
+                }
+            }
+        }
+
+        /// Only known codes are converted, use from_raw() to keep unknown
+        /// ones.
+        impl ::num::FromPrimitive for $enum {
+            fn from_i64(n: i64) -> Option<$enum> {
+                if n < i64::from(i32::MIN) || n > i64::from(i32::MAX) {
+                    return None;
+                }
+                $enum::from_known(n as i32)
+            }
+
+            fn from_u64(n: u64) -> Option<$enum> {
+                if n > i32::MAX as u64 {
+                    return None;
+                }
+                $enum::from_known(n as i32)
+            }
+        }
+
+        /// Accepts symbolic name in any case ("ENOENT", "enoent"), raw number
+        /// ("2") or number marked with OS it's from ("linux:44"), as Host
+        /// errors are printed. Unknown numbers end up in Unknown, 0 is an
+        /// error.
+        impl ::std::str::FromStr for $enum {
+            type Err = $crate::ParseErrnoError;
+
+            fn from_str(s: &str) -> Result<$enum, $crate::ParseErrnoError> {
+                let name = s.trim();
+                if let Some((os, code)) = name.split_once(':') {
+                    return code
+                        .parse()
+                        .ok()
+                        .and_then(|code| $enum::from_marked_raw(os, code))
+                        .ok_or_else(|| $crate::ParseErrnoError::new(s));
+                }
+                if let Ok(code) = name.parse::<i32>() {
+                    return $enum::from_raw(code).ok_or_else(|| $crate::ParseErrnoError::new(s));
+                }
+                $enum::TABLE
+                    .iter()
+                    .find(|&&(n, _)| n.eq_ignore_ascii_case(name))
+                    .and_then(|&(_, code)| $enum::from_known(code))
+                    .ok_or_else(|| $crate::ParseErrnoError::new(s))
+            }
+        }
+
+        /// Known errors are serialized by name ("ENOENT"), unknown ones by
+        /// number and Host ones by number marked with the host OS
+        /// ("linux:44"). All forms are accepted back.
+        #[cfg(feature = "serde")]
+        impl ::serde::Serialize for $enum {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                match *self {
+                    $enum::Unknown(code) => serializer.serialize_i32(code),
+                    $enum::Host(code) => {
+                        let marker = $crate::translate::host_marker();
+                        serializer.collect_str(&format_args!("{}:{}", marker, code))
+                    }
+                    _ => serializer.serialize_str(self.name()),
+                }
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> ::serde::Deserialize<'de> for $enum {
+            fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<$enum, D::Error> {
+                struct Visitor;
+
+                impl<'de> ::serde::de::Visitor<'de> for Visitor {
+                    type Value = $enum;
+
+                    fn expecting(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                        f.write_str("errno name or non-zero number")
+                    }
+
+                    fn visit_str<E: ::serde::de::Error>(self, v: &str) -> Result<$enum, E> {
+                        v.parse().map_err(E::custom)
+                    }
+
+                    fn visit_i64<E: ::serde::de::Error>(self, v: i64) -> Result<$enum, E> {
+                        if v < i64::from(i32::MIN) || v > i64::from(i32::MAX) {
+                            return Err(E::custom(format!("errno out of range: {}", v)));
+                        }
+                        $enum::from_raw(v as i32).ok_or_else(|| E::custom("errno can't be 0"))
+                    }
+
+                    fn visit_u64<E: ::serde::de::Error>(self, v: u64) -> Result<$enum, E> {
+                        if v > i32::MAX as u64 {
+                            return Err(E::custom(format!("errno out of range: {}", v)));
+                        }
+                        self.visit_i64(v as i64)
+                    }
+                }
+
+                deserializer.deserialize_any(Visitor)
+            }
+        }
+
+        impl ::std::error::Error for $enum {
+            fn description(&self) -> &str {
+                self.long_description()
+            }
+        }
+
+        /// Prints "ENOENT: No such file or directory". Alternate form ("{:#}")
+        /// prints long description instead of short message. Unknown errors
+        /// print their code as "UNKNOWN(1234): Unknown error", Host ones also
+        /// the host OS, e.g. "UNKNOWN(linux:44): Unknown error".
+        impl ::std::fmt::Display for $enum {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                match *self {
+                    $enum::Unknown(code) => write!(f, "{}({})", self.name(), code)?,
+                    $enum::Host(code) => {
+                        let marker = $crate::translate::host_marker();
+                        write!(f, "{}({}:{})", self.name(), marker, code)?
+                    }
+                    _ => f.write_str(self.name())?,
+                }
+                if f.alternate() {
+                    write!(f, ": {}", self.long_description())
+                } else {
+                    write!(f, ": {}", self.short_message())
+                }
+            }
+        }
+    }
+}
+
+pub(crate) const fn min_code(table: &[(&str, i32)]) -> i32 {
+    let mut min = i32::MAX;
+    let mut i = 0;
+    while i < table.len() {
+        if table[i].1 < min {
+            min = table[i].1;
+        }
+        i += 1;
+    }
+    min
+}
+
+pub(crate) const fn max_code(table: &[(&str, i32)]) -> i32 {
+    let mut max = i32::MIN;
+    let mut i = 0;
+    while i < table.len() {
+        if table[i].1 > max {
+            max = table[i].1;
+        }
+        i += 1;
+    }
+    max
+}
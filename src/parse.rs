@@ -0,0 +1,27 @@
+use std::error::Error;
+use std::fmt;
+
+/// Returned when string is neither a known errno name nor a non-zero number.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseErrnoError {
+    input: String,
+}
+
+impl ParseErrnoError {
+    pub(crate) fn new(input: &str) -> ParseErrnoError {
+        ParseErrnoError { input: input.to_owned() }
+    }
+
+    /// String that failed to parse.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+impl Error for ParseErrnoError {}
+
+impl fmt::Display for ParseErrnoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown errno: {:?}", self.input)
+    }
+}
@@ -0,0 +1,263 @@
+use std::collections::BTreeMap;
+use std::error::Error as StdError;
+use std::fmt::Write;
+
+use {Blunder, BsdError, HttpStatus};
+
+/// Members defined by RFC 7807 itself, extensions can't use these names.
+const STANDARD_MEMBERS: &[&str] = &["type", "title", "status", "detail", "instance"];
+
+/// Error kind that can be rendered as RFC 7807 problem details. Everything
+/// has a default, so `impl ProblemType for AppError {}` is enough to start.
+pub trait ProblemType: StdError {
+    /// URI identifying the problem type. "about:blank" means there is nothing
+    /// to it beyond the HTTP status.
+    fn problem_type(&self) -> String {
+        "about:blank".to_owned()
+    }
+
+    /// HTTP status code for the problem.
+    fn problem_status(&self) -> u16 {
+        500
+    }
+
+    /// Short summary that doesn't change from occurrence to occurrence.
+    fn problem_title(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// There is no type URI for errno, status comes from HttpStatus.
+impl ProblemType for BsdError {
+    fn problem_status(&self) -> u16 {
+        self.http_status()
+    }
+
+    fn problem_title(&self) -> String {
+        self.short_message().to_owned()
+    }
+}
+
+/// RFC 7807 problem details object, i.e. body of application/problem+json
+/// response. Made by Blunder::problem_details().
+///
+/// ```
+/// # use blunder::{Blunder, BsdError};
+/// let err = Blunder::with_detail(BsdError::ENOENT, "no such user");
+/// let problem = err.problem_details().with_instance("/users/42");
+/// assert_eq!(
+///     problem.to_json(),
+///     r#"{"type":"about:blank","title":"No such file or directory","status":404,"detail":"no such user","instance":"/users/42"}"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProblemDetails {
+    problem_type: String,
+    title: String,
+    status: u16,
+    detail: Option<String>,
+    instance: Option<String>,
+    extensions: BTreeMap<String, String>,
+}
+
+impl ProblemDetails {
+    /// Media type to send it with.
+    pub const CONTENT_TYPE: &'static str = "application/problem+json";
+
+    /// URI of the occurrence, usually path of the request.
+    pub fn with_instance<S: Into<String>>(mut self, instance: S) -> ProblemDetails {
+        self.instance = Some(instance.into());
+        self
+    }
+
+    /// Add extension member, e.g. "balance" or "request_id". Names of the
+    /// standard members are ignored.
+    pub fn with_extension<K, V>(mut self, name: K, value: V) -> ProblemDetails
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let name = name.into();
+        if !STANDARD_MEMBERS.contains(&name.as_str()) {
+            self.extensions.insert(name, value.into());
+        }
+        self
+    }
+
+    pub fn problem_type(&self) -> &str {
+        &self.problem_type
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    pub fn detail(&self) -> Option<&str> {
+        self.detail.as_deref()
+    }
+
+    pub fn instance(&self) -> Option<&str> {
+        self.instance.as_deref()
+    }
+
+    pub fn extensions(&self) -> &BTreeMap<String, String> {
+        &self.extensions
+    }
+
+    /// Render as JSON object. Members go in RFC order, extensions after them
+    /// sorted by name. Absent detail and instance are left out.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{");
+        push_member(&mut json, "type", &self.problem_type);
+        push_member(&mut json, "title", &self.title);
+        write!(json, ",\"status\":{}", self.status).unwrap();
+        if let Some(ref detail) = self.detail {
+            push_member(&mut json, "detail", detail);
+        }
+        if let Some(ref instance) = self.instance {
+            push_member(&mut json, "instance", instance);
+        }
+        for (name, value) in &self.extensions {
+            push_member(&mut json, name, value);
+        }
+        json.push('}');
+        json
+    }
+}
+
+fn push_member(json: &mut String, name: &str, value: &str) {
+    if json.len() > 1 {
+        json.push(',');
+    }
+    push_string(json, name);
+    json.push(':');
+    push_string(json, value);
+}
+
+fn push_string(json: &mut String, s: &str) {
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c < ' ' => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for ProblemDetails {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", &self.problem_type)?;
+        map.serialize_entry("title", &self.title)?;
+        map.serialize_entry("status", &self.status)?;
+        if let Some(ref detail) = self.detail {
+            map.serialize_entry("detail", detail)?;
+        }
+        if let Some(ref instance) = self.instance {
+            map.serialize_entry("instance", instance)?;
+        }
+        for (name, value) in &self.extensions {
+            map.serialize_entry(name, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<T: ProblemType + Clone> Blunder<T> {
+    /// Problem details with type, title and status from the kind and detail
+    /// from Blunder::detail(). Add instance and extensions on top.
+    pub fn problem_details(&self) -> ProblemDetails {
+        ProblemDetails::from(self)
+    }
+}
+
+impl<'a, T: ProblemType + Clone> From<&'a Blunder<T>> for ProblemDetails {
+    fn from(blunder: &'a Blunder<T>) -> ProblemDetails {
+        ProblemDetails {
+            problem_type: blunder.kind.problem_type(),
+            title: blunder.kind.problem_title(),
+            status: blunder.kind.problem_status(),
+            detail: blunder.detail(),
+            instance: None,
+            extensions: BTreeMap::new(),
+        }
+    }
+}
+
+#[test]
+fn problem_details() {
+    use std::fmt;
+
+    #[derive(Debug, Clone)]
+    struct OutOfCredit;
+
+    impl fmt::Display for OutOfCredit {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("You do not have enough credit.")
+        }
+    }
+
+    impl StdError for OutOfCredit {}
+
+    impl ProblemType for OutOfCredit {
+        fn problem_type(&self) -> String {
+            "https://example.com/probs/out-of-credit".to_owned()
+        }
+
+        fn problem_status(&self) -> u16 {
+            403
+        }
+    }
+
+    let err = Blunder::with_detail(OutOfCredit, "Your current balance is 30, but that costs 50.");
+    let problem = err
+        .problem_details()
+        .with_instance("/account/12345/msgs/abc")
+        .with_extension("balance", "30")
+        .with_extension("status", "200");
+    assert_eq!(problem.status(), 403);
+    assert_eq!(problem.title(), "You do not have enough credit.");
+    assert_eq!(problem.instance(), Some("/account/12345/msgs/abc"));
+    assert_eq!(problem.extensions().len(), 1);
+    assert_eq!(
+        problem.to_json(),
+        "{\"type\":\"https://example.com/probs/out-of-credit\",\
+         \"title\":\"You do not have enough credit.\",\"status\":403,\
+         \"detail\":\"Your current balance is 30, but that costs 50.\",\
+         \"instance\":\"/account/12345/msgs/abc\",\"balance\":\"30\"}"
+    );
+
+    let problem = Blunder::new(BsdError::EIO).problem_details().with_extension("path", "C:\\\"x\"\n");
+    assert_eq!(problem.detail(), None);
+    assert_eq!(
+        problem.to_json(),
+        r#"{"type":"about:blank","title":"Input/output error","status":500,"path":"C:\\\"x\"\n"}"#
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn problem_details_serde() {
+    extern crate serde_json;
+
+    let problem = Blunder::with_detail(BsdError::ENOENT, "no \"such\" user")
+        .problem_details()
+        .with_instance("/users/42")
+        .with_extension("errno", "ENOENT")
+        .with_extension("\u{1}", "\u{7f}");
+    let json: serde_json::Value = serde_json::from_str(&problem.to_json()).unwrap();
+    assert_eq!(json, serde_json::to_value(&problem).unwrap());
+}
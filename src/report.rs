@@ -0,0 +1,191 @@
+use std::any::Any;
+use std::error::Error as StdError;
+use std::fmt;
+
+use {Blunder, BsdError, LinuxError};
+
+const RED_BOLD: &str = "\x1b[1;31m";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// Multi-line diagnostic for Blunder. Made by Blunder::report().
+///
+/// ```text
+/// error: ENOENT: No such file or directory
+///     No such file or directory. A component of a specified pathname did not
+///     exist, or the pathname was an empty string.
+///     detail: can't load /etc/rc.conf
+///     at src/main.rs:10:5 (app::config)
+///
+/// Caused by:
+///     0: ...
+/// ```
+pub struct Report<'a, T: StdError + Clone + 'a> {
+    blunder: &'a Blunder<T>,
+    color: bool,
+    width: usize,
+}
+
+impl<'a, T: StdError + Clone + 'a> Report<'a, T> {
+    pub fn new(blunder: &'a Blunder<T>) -> Report<'a, T> {
+        Report {
+            blunder,
+            color: false,
+            width: 80,
+        }
+    }
+
+    /// Highlight with ANSI escape codes. Off by default.
+    pub fn color(mut self, color: bool) -> Report<'a, T> {
+        self.color = color;
+        self
+    }
+
+    /// Wrap long lines to fit into given amount of columns. 80 by default,
+    /// 0 disables wrapping.
+    pub fn width(mut self, width: usize) -> Report<'a, T> {
+        self.width = width;
+        self
+    }
+
+    fn paint(&self, f: &mut fmt::Formatter, style: &str, text: &str) -> fmt::Result {
+        if self.color {
+            write!(f, "{}{}{}", style, text, RESET)
+        } else {
+            f.write_str(text)
+        }
+    }
+
+    fn write_wrapped(&self, f: &mut fmt::Formatter, indent: &str, text: &str) -> fmt::Result {
+        for line in wrap(text, self.width.saturating_sub(indent.len())) {
+            writeln!(f, "{}{}", indent, line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Long description goes under the first line, errno kinds are the only ones
+/// that have it.
+impl<'a, T: StdError + Clone + 'static> fmt::Display for Report<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const INDENT: &str = "    ";
+        let kind = &self.blunder.kind;
+
+        self.paint(f, RED_BOLD, "error")?;
+        writeln!(f, ": {}", kind)?;
+
+        if let Some(description) = long_description(kind) {
+            self.write_wrapped(f, INDENT, description)?;
+        }
+        if let Some(ref detail) = self.blunder.detail {
+            self.write_wrapped(f, INDENT, &format!("detail: {}", detail))?;
+        }
+        if let Some(location) = self.blunder.location() {
+            f.write_str(INDENT)?;
+            self.paint(f, DIM, &format!("at {}", location))?;
+            writeln!(f)?;
+        }
+
+        let mut source = self.blunder.source();
+        if source.is_some() {
+            writeln!(f)?;
+            self.paint(f, BOLD, "Caused by:")?;
+            writeln!(f)?;
+        }
+        let mut idx = 0;
+        while let Some(err) = source {
+            let prefix = format!("{}{}: ", INDENT, idx);
+            let indent = " ".repeat(prefix.len());
+            let width = self.width.saturating_sub(prefix.len());
+            for (n, line) in wrap(&err.to_string(), width).iter().enumerate() {
+                writeln!(f, "{}{}", if n == 0 { &prefix } else { &indent }, line)?;
+            }
+            source = err.source();
+            idx += 1;
+        }
+
+        #[cfg(feature = "backtrace")]
+        {
+            use std::backtrace::BacktraceStatus;
+
+            let backtrace = self.blunder.backtrace();
+            if backtrace.status() == BacktraceStatus::Captured {
+                writeln!(f)?;
+                self.paint(f, BOLD, "Stack backtrace:")?;
+                writeln!(f)?;
+                write!(f, "{}", backtrace)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn long_description(kind: &dyn Any) -> Option<&'static str> {
+    if let Some(err) = kind.downcast_ref::<BsdError>() {
+        return Some(err.long_description());
+    }
+    kind.downcast_ref::<LinuxError>().map(|err| err.long_description())
+}
+
+/// Greedy word wrap. Words longer than width get a line of their own.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && width > 0 && line.len() + 1 + word.len() > width {
+            lines.push(line);
+            line = String::new();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+#[test]
+fn report() {
+    use std::io;
+    use BsdError;
+    use Location;
+
+    let io = io::Error::other("disk on fire");
+    let err = Blunder::builder(BsdError::ENOENT)
+        .detail("can't load /etc/rc.conf")
+        .source(Blunder::caused_by(BsdError::EIO, io).located_at(Location::new("io.rs", 1, 2, None)))
+        .location(Location::new("src/main.rs", 10, 5, Some("app::config")))
+        .build();
+
+    let expected = format!(
+        "error: {}\n    No such file or directory. A component of a specified pathname did not\n    \
+         exist, or the pathname was an empty string.\n    detail: can't load /etc/rc.conf\n    \
+         at src/main.rs:10:5 (app::config)\n\nCaused by:\n    0: Input/output error. Some \
+         physical input or output error occurred. This\n       error will not be reported until \
+         a subsequent operation on the same file\n       descriptor and may be lost (over \
+         written) by any subsequent errors. at\n       io.rs:1:2\n    1: disk on fire\n",
+        BsdError::ENOENT
+    );
+    #[cfg(not(feature = "backtrace"))]
+    assert_eq!(err.report().to_string(), expected);
+    #[cfg(feature = "backtrace")]
+    assert!(err.report().to_string().starts_with(&expected));
+
+    let colored = err.report().color(true).to_string();
+    assert!(colored.starts_with("\x1b[1;31merror\x1b[0m: "));
+    assert!(colored.contains("\x1b[1mCaused by:\x1b[0m\n"));
+
+    // Kinds other than errno have nothing but Display
+    let kind = "EWAT".parse::<BsdError>().unwrap_err();
+    let err = Blunder::new(kind).located_at(Location::new("x.rs", 1, 2, None));
+    let report = err.report().to_string();
+    assert!(report.starts_with("error: unknown errno: \"EWAT\"\n    at x.rs:1:2\n"));
+
+    assert_eq!(wrap("a  bb ccc", 4), vec!["a bb", "ccc"]);
+    assert_eq!(wrap("a  bb ccc", 0), vec!["a bb ccc"]);
+    assert_eq!(wrap("", 4), vec![""]);
+}
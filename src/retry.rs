@@ -0,0 +1,263 @@
+//! Retry operations that fail with transient errors.
+//!
+//! ```
+//! # use blunder::{Blunder, BsdError};
+//! # use blunder::retry::{retry, RetryPolicy};
+//! # use std::time::Duration;
+//! let policy = RetryPolicy::default().initial_delay(Duration::from_millis(1));
+//! let mut calls = 0;
+//! let res = retry(&policy, || {
+//!     calls += 1;
+//!     if calls < 3 {
+//!         Err(Blunder::new(BsdError::EINTR))
+//!     } else {
+//!         Ok(calls)
+//!     }
+//! });
+//! assert_eq!(res.unwrap(), 3);
+//! ```
+use std::collections::hash_map::RandomState;
+use std::error::Error as StdError;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use {Blunder, BsdError};
+
+type Predicate<E> = Box<dyn Fn(&Blunder<E>) -> bool + Send + Sync>;
+
+/// How many times and how often to retry. Delay between attempts starts at
+/// initial_delay and doubles after every attempt up to max_delay. With jitter
+/// every delay is randomly shortened by up to given fraction of it.
+pub struct RetryPolicy<E: StdError + Clone> {
+    max_attempts: u32,
+    initial_delay: Duration,
+    max_delay: Duration,
+    jitter: f64,
+    deadline: Option<Duration>,
+    predicate: Predicate<E>,
+}
+
+impl<E: StdError + Clone> RetryPolicy<E> {
+    /// Policy that retries errors for which predicate returns true. 5
+    /// attempts, 10ms to 1s delay with 0.5 jitter and no deadline.
+    pub fn new<F>(predicate: F) -> RetryPolicy<E>
+    where
+        F: Fn(&Blunder<E>) -> bool + Send + Sync + 'static,
+    {
+        RetryPolicy {
+            max_attempts: 5,
+            initial_delay: Duration::from_millis(10),
+            max_delay: Duration::from_secs(1),
+            jitter: 0.5,
+            deadline: None,
+            predicate: Box::new(predicate),
+        }
+    }
+
+    /// Total number of attempts, including the first one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> RetryPolicy<E> {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn initial_delay(mut self, delay: Duration) -> RetryPolicy<E> {
+        self.initial_delay = delay;
+        self
+    }
+
+    pub fn max_delay(mut self, delay: Duration) -> RetryPolicy<E> {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Fraction between 0 and 1, 0 disables jitter. NaN and infinities are
+    /// taken as 0.
+    pub fn jitter(mut self, jitter: f64) -> RetryPolicy<E> {
+        self.jitter = if jitter.is_finite() { jitter.clamp(0.0, 1.0) } else { 0.0 };
+        self
+    }
+
+    /// Give up if next attempt would start later than this after the first.
+    pub fn deadline(mut self, deadline: Duration) -> RetryPolicy<E> {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Replace the predicate that decides what is worth retrying.
+    pub fn retry_if<F>(mut self, predicate: F) -> RetryPolicy<E>
+    where
+        F: Fn(&Blunder<E>) -> bool + Send + Sync + 'static,
+    {
+        self.predicate = Box::new(predicate);
+        self
+    }
+
+    fn delay(&self, attempt: u32) -> Duration {
+        let delay = self.initial_delay
+            .checked_mul(1 << attempt.min(31))
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+        if self.jitter == 0.0 {
+            return delay;
+        }
+        delay.mul_f64(1.0 - self.jitter * random_fraction())
+    }
+}
+
+/// Retries transient errors, see BsdError::is_transient().
+impl Default for RetryPolicy<BsdError> {
+    fn default() -> RetryPolicy<BsdError> {
+        RetryPolicy::new(|err: &Blunder<BsdError>| err.kind().is_transient())
+    }
+}
+
+impl<E: StdError + Clone> fmt::Debug for RetryPolicy<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_delay", &self.initial_delay)
+            .field("max_delay", &self.max_delay)
+            .field("jitter", &self.jitter)
+            .field("deadline", &self.deadline)
+            .finish()
+    }
+}
+
+/// Errors of every failed attempt, in order. Put as source of the Blunder
+/// returned by retry() when there was more than one attempt.
+#[derive(Debug)]
+pub struct RetryError<E: StdError + Clone> {
+    attempts: Vec<Blunder<E>>,
+}
+
+impl<E: StdError + Clone> RetryError<E> {
+    pub fn attempts(&self) -> &[Blunder<E>] {
+        &self.attempts
+    }
+}
+
+impl<E: StdError + Clone> fmt::Display for RetryError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} attempts failed", self.attempts.len())
+    }
+}
+
+/// Source is the last attempt, so the chain continues with its causes.
+impl<E: StdError + Clone + Send + Sync + 'static> StdError for RetryError<E> {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.attempts.last().map(|last| last as &(dyn StdError + 'static))
+    }
+}
+
+/// Run op until it succeeds, fails with an error policy doesn't consider
+/// worth retrying, or policy runs out of attempts or time. If op failed more
+/// than once, returned Blunder has kind of the last error and RetryError with
+/// all of them as source.
+#[track_caller]
+pub fn retry<T, E, F>(policy: &RetryPolicy<E>, mut op: F) -> Result<T, Blunder<E>>
+where
+    E: StdError + Clone + Send + Sync + 'static,
+    F: FnMut() -> Result<T, Blunder<E>>,
+{
+    let started = Instant::now();
+    let mut attempts = Vec::new();
+    loop {
+        let err = match op() {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+        let attempt = attempts.len() as u32;
+        let give_up = !(policy.predicate)(&err) || attempt + 1 >= policy.max_attempts;
+        attempts.push(err);
+        if give_up {
+            break;
+        }
+
+        let delay = policy.delay(attempt);
+        if let Some(deadline) = policy.deadline {
+            if started.elapsed() + delay > deadline {
+                break;
+            }
+        }
+        thread::sleep(delay);
+    }
+
+    if attempts.len() == 1 {
+        return Err(attempts.pop().unwrap());
+    }
+    let kind = attempts[attempts.len() - 1].kind();
+    let detail = format!("gave up after {} attempts", attempts.len());
+    let mut blunder = Blunder::caused_by(kind, RetryError { attempts });
+    blunder.set_detail(detail);
+    Err(blunder)
+}
+
+/// Random number in [0, 1) without pulling rand in. RandomState is seeded
+/// randomly for every instance, which is good enough for jitter.
+fn random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(0);
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[test]
+fn retry_transient() {
+    let policy = RetryPolicy::default()
+        .max_attempts(3)
+        .initial_delay(Duration::from_millis(1));
+
+    let mut calls = 0;
+    let err = retry(&policy, || -> Result<(), Blunder<BsdError>> {
+        calls += 1;
+        Err(Blunder::with_detail(BsdError::EAGAIN, format!("call {}", calls)))
+    }).unwrap_err();
+    assert_eq!(calls, 3);
+    assert_eq!(err.kind(), BsdError::EAGAIN);
+    assert_eq!(err.detail(), Some("gave up after 3 attempts".to_owned()));
+    let retry_err = err.source().unwrap().downcast_ref::<RetryError<BsdError>>().unwrap();
+    let details: Vec<_> = retry_err.attempts().iter().map(|a| a.detail().unwrap()).collect();
+    assert_eq!(details, vec!["call 1", "call 2", "call 3"]);
+
+    let mut calls = 0;
+    let err = retry(&policy, || -> Result<(), Blunder<BsdError>> {
+        calls += 1;
+        Err(Blunder::new(BsdError::EINVAL))
+    }).unwrap_err();
+    assert_eq!(calls, 1);
+    assert!(err.source().is_none());
+
+    let policy = policy.max_attempts(10).deadline(Duration::from_millis(0));
+    let mut calls = 0;
+    let _ = retry(&policy, || -> Result<(), Blunder<BsdError>> {
+        calls += 1;
+        Err(Blunder::new(BsdError::EINTR))
+    });
+    assert_eq!(calls, 1);
+}
+
+#[test]
+fn retry_delay() {
+    let policy = RetryPolicy::default()
+        .initial_delay(Duration::from_millis(10))
+        .max_delay(Duration::from_millis(50))
+        .jitter(0.0);
+    assert_eq!(policy.delay(0), Duration::from_millis(10));
+    assert_eq!(policy.delay(2), Duration::from_millis(40));
+    assert_eq!(policy.delay(3), Duration::from_millis(50));
+    assert_eq!(policy.delay(100), Duration::from_millis(50));
+
+    for &jitter in &[f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        let policy = RetryPolicy::default().max_attempts(2).initial_delay(Duration::ZERO);
+        let policy = policy.jitter(jitter);
+        assert_eq!(policy.jitter, 0.0);
+        assert!(retry(&policy, || Err::<(), _>(Blunder::new(BsdError::EAGAIN))).is_err());
+    }
+
+    let policy = policy.jitter(0.5);
+    for attempt in 0..10 {
+        let delay = policy.delay(attempt);
+        assert!(delay <= Duration::from_millis(50) && delay >= Duration::from_millis(5));
+    }
+}
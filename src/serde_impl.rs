@@ -0,0 +1,210 @@
+use serde::de::Deserializer;
+use serde::ser::{SerializeStruct, Serializer};
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::error::Error as StdError;
+use std::fmt;
+
+use {Blunder, BsdError, LinuxError, Location};
+
+/// Blunder is serialized as
+/// `{"kind":"ENOENT","code":2,"detail":...,"location":{...},"sources":[...]}`.
+/// Code is only there for errno kinds and is in numbering of their table, so
+/// Host errors have none, their kind already says which OS they're from.
+/// Sources are Display of every error in the chain. Backtrace is not
+/// serialized.
+impl<T> Serialize for Blunder<T>
+where
+    T: StdError + Clone + Serialize + 'static,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let code = raw_code(&self.kind);
+        let mut sources = Vec::new();
+        let mut source = StdError::source(self);
+        while let Some(err) = source {
+            sources.push(err.to_string());
+            source = err.source();
+        }
+
+        let len = if code.is_some() { 5 } else { 4 };
+        let mut state = serializer.serialize_struct("Blunder", len)?;
+        state.serialize_field("kind", &self.kind)?;
+        if let Some(code) = code {
+            state.serialize_field("code", &code)?;
+        }
+        state.serialize_field("detail", &self.detail)?;
+        state.serialize_field("location", &self.location)?;
+        state.serialize_field("sources", &sources)?;
+        state.end()
+    }
+}
+
+fn raw_code(kind: &dyn Any) -> Option<i32> {
+    match kind.downcast_ref::<BsdError>() {
+        Some(&BsdError::Host(_)) => return None,
+        Some(err) => return Some(err.raw_os_error()),
+        None => {}
+    }
+    match kind.downcast_ref::<LinuxError>() {
+        Some(&LinuxError::Host(_)) | None => None,
+        Some(err) => Some(err.raw_os_error()),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Blunder")]
+struct BlunderRepr<T> {
+    kind: T,
+    #[serde(default)]
+    detail: Option<String>,
+    #[serde(default)]
+    location: Option<Location>,
+    #[serde(default)]
+    sources: Vec<String>,
+}
+
+/// Sources come back as plain messages chained one after another, original
+/// error types are lost. Backtrace is left disabled, trace of deserializer is
+/// of no use to anyone.
+impl<'de, T> Deserialize<'de> for Blunder<T>
+where
+    T: StdError + Clone + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Blunder<T>, D::Error> {
+        let repr = BlunderRepr::<T>::deserialize(deserializer)?;
+        let mut blunder = Blunder::new(repr.kind);
+        blunder.detail = repr.detail;
+        blunder.location = repr.location;
+        blunder.source = repr
+            .sources
+            .into_iter()
+            .rev()
+            .fold(None, |source, message| Some(Box::new(RemoteError { message, source })))
+            .map(|err| err as Box<dyn StdError + Send + Sync>);
+        #[cfg(feature = "backtrace")]
+        {
+            blunder.backtrace = Box::new(::std::backtrace::Backtrace::disabled());
+        }
+        Ok(blunder)
+    }
+}
+
+/// Error that was serialized on the other side.
+#[derive(Debug)]
+struct RemoteError {
+    message: String,
+    source: Option<Box<RemoteError>>,
+}
+
+impl StdError for RemoteError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.source.as_ref().map(|err| &**err as &(dyn StdError + 'static))
+    }
+}
+
+impl fmt::Display for RemoteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Serialize for Location {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Location", 4)?;
+        state.serialize_field("file", self.file())?;
+        state.serialize_field("line", &self.line())?;
+        state.serialize_field("column", &self.column())?;
+        state.serialize_field("module_path", &self.module_path())?;
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Location")]
+struct LocationRepr {
+    file: String,
+    line: u32,
+    column: u32,
+    #[serde(default)]
+    module_path: Option<String>,
+}
+
+impl<'de> Deserialize<'de> for Location {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Location, D::Error> {
+        let repr = LocationRepr::deserialize(deserializer)?;
+        Ok(Location::from_owned(repr.file, repr.line, repr.column, repr.module_path))
+    }
+}
+
+#[test]
+fn serde_blunder() {
+    extern crate serde_json;
+    use std::io;
+
+    let io = io::Error::other("disk on fire");
+    let err = Blunder::builder(BsdError::ENOENT)
+        .detail("no config")
+        .source(Blunder::caused_by(BsdError::EIO, io))
+        .location(Location::new("src/main.rs", 42, 13, Some("app")))
+        .build();
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "kind": "ENOENT",
+            "code": 2,
+            "detail": "no config",
+            "location": {"file": "src/main.rs", "line": 42, "column": 13, "module_path": "app"},
+            "sources": [err.source().unwrap().to_string(), "disk on fire"],
+        })
+    );
+
+    let back: Blunder<BsdError> = serde_json::from_value(json).unwrap();
+    assert_eq!(back, err);
+    assert_eq!(back.location(), err.location());
+    assert_eq!(back.source().unwrap().source().unwrap().to_string(), "disk on fire");
+    #[cfg(not(feature = "backtrace"))]
+    assert_eq!(back.report().to_string(), err.report().to_string());
+
+    let back: Blunder<BsdError> = serde_json::from_str(r#"{"kind":1000}"#).unwrap();
+    assert_eq!(back.kind(), BsdError::Unknown(1000));
+    assert_eq!(back.location(), None);
+
+    // ECHRNG from a Linux worker, 44 alone would read as ESOCKTNOSUPPORT
+    #[cfg(target_os = "linux")]
+    {
+        let err = Blunder::new(BsdError::from_host_raw(::libc::ECHRNG).unwrap());
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["kind"], "linux:44");
+        assert!(json.get("code").is_none());
+        let back: Blunder<BsdError> = serde_json::from_value(json).unwrap();
+        assert_eq!(back.kind(), BsdError::Host(::libc::ECHRNG));
+    }
+}
+
+#[test]
+fn serde_errno() {
+    extern crate serde_json;
+
+    assert_eq!(serde_json::to_string(&BsdError::EAGAIN).unwrap(), r#""EAGAIN""#);
+    assert_eq!(serde_json::to_string(&BsdError::Unknown(1000)).unwrap(), "1000");
+    assert_eq!(serde_json::to_string(&LinuxError::EAGAIN).unwrap(), r#""EAGAIN""#);
+    for err in BsdError::all().chain(Some(BsdError::Unknown(1000))) {
+        let json = serde_json::to_string(&err).unwrap();
+        assert_eq!(serde_json::from_str::<BsdError>(&json).unwrap(), err);
+    }
+    assert_eq!(serde_json::from_str::<BsdError>("2").unwrap(), BsdError::ENOENT);
+    assert_eq!(serde_json::from_str::<BsdError>(r#""enoent""#).unwrap(), BsdError::ENOENT);
+    assert!(serde_json::from_str::<BsdError>("0").is_err());
+    assert!(serde_json::from_str::<BsdError>(r#""EWAT""#).is_err());
+
+    #[cfg(target_os = "linux")]
+    {
+        ::errno::set_errno(::errno::Errno(::libc::ECHRNG));
+        let err = ::cvt(-1).unwrap_err().kind();
+        let json = serde_json::to_string(&err).unwrap();
+        assert_eq!(json, r#""linux:44""#);
+        assert_eq!(serde_json::from_str::<BsdError>(&json).unwrap(), err);
+        assert_eq!(serde_json::from_str::<LinuxError>(&json).unwrap(), LinuxError::ECHRNG);
+    }
+}
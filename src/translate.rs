@@ -0,0 +1,265 @@
+use BsdError;
+use LinuxError;
+
+/// Operating systems with known errno numbering.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Os {
+    FreeBsd,
+    Linux,
+    MacOs,
+    Illumos,
+}
+
+impl Os {
+    /// OS crate is compiled for, if its numbering is known.
+    pub fn host() -> Option<Os> {
+        if cfg!(target_os = "freebsd") {
+            Some(Os::FreeBsd)
+        } else if cfg!(any(target_os = "linux", target_os = "android")) {
+            Some(Os::Linux)
+        } else if cfg!(any(target_os = "macos", target_os = "ios")) {
+            Some(Os::MacOs)
+        } else if cfg!(any(target_os = "illumos", target_os = "solaris")) {
+            Some(Os::Illumos)
+        } else {
+            None
+        }
+    }
+
+    /// Lowercase name, used to mark codes in this numbering, e.g. "linux:44".
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Os::FreeBsd => "freebsd",
+            Os::Linux => "linux",
+            Os::MacOs => "macos",
+            Os::Illumos => "illumos",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Os> {
+        [Os::FreeBsd, Os::Linux, Os::MacOs, Os::Illumos]
+            .iter()
+            .cloned()
+            .find(|os| os.name().eq_ignore_ascii_case(name))
+    }
+
+    fn table(&self) -> &'static [(&'static str, i32)] {
+        match *self {
+            Os::FreeBsd => BsdError::TABLE,
+            Os::Linux => LinuxError::TABLE,
+            Os::MacOs => MACOS,
+            Os::Illumos => ILLUMOS,
+        }
+    }
+
+    /// Symbolic name of the code on this OS, e.g. "EAGAIN" for 35 on FreeBSD.
+    pub fn name_of(&self, code: i32) -> Option<&'static str> {
+        self.table().iter().find(|&&(_, c)| c == code).map(|&(name, _)| name)
+    }
+
+    /// Code for the symbolic name on this OS, e.g. 11 for "EAGAIN" on Linux.
+    pub fn code_of(&self, name: &str) -> Option<i32> {
+        self.table().iter().find(|&&(n, _)| n == name).map(|&(_, code)| code)
+    }
+}
+
+/// Translate errno from one OS numbering to another by its symbolic name.
+/// Returns None if the code is not known on the source OS or there is no
+/// error with such name on the target OS (e.g. ECAPMODE on Linux).
+///
+/// ```
+/// # use blunder::{translate, Os};
+/// assert_eq!(translate(35, Os::FreeBsd, Os::Linux), Some(11)); // EAGAIN
+/// assert_eq!(translate(94, Os::FreeBsd, Os::Linux), None); // ECAPMODE
+/// ```
+pub fn translate(code: i32, from: Os, to: Os) -> Option<i32> {
+    from.name_of(code).and_then(|name| to.code_of(name))
+}
+
+/// Marks Host errors in Display and serde, name of the host OS or "host" if
+/// its numbering is not known.
+pub(crate) fn host_marker() -> &'static str {
+    Os::host().map_or("host", |os| os.name())
+}
+
+/// from_host_raw() and to_host_raw() for errno enum numbered as given OS.
+macro_rules! host_raw {
+    ($enum:ident, $os:expr) => {
+        impl $enum {
+            /// Error code of the host OS in terms of this table. Codes that
+            /// can't be translated end up in Host. Returns None only for 0.
+            pub fn from_host_raw(code: i32) -> Option<$enum> {
+                if code == 0 {
+                    return None;
+                }
+                match Os::host() {
+                    Some(host) if host == $os => $enum::from_raw(code),
+                    Some(host) => translate(code, host, $os)
+                        .and_then($enum::from_raw)
+                        .or(Some($enum::Host(code))),
+                    None => Some($enum::Host(code)),
+                }
+            }
+
+            /// Reverse of from_host_raw(). None if the host has no such error,
+            /// Unknown errors are only returned if the host is numbered the
+            /// same as this table.
+            pub fn to_host_raw(&self) -> Option<i32> {
+                if let $enum::Host(code) = *self {
+                    return Some(code);
+                }
+                match Os::host() {
+                    Some(host) if host == $os => Some(self.raw_os_error()),
+                    Some(host) => translate(self.raw_os_error(), $os, host),
+                    None => None,
+                }
+            }
+
+            /// Code marked with OS it's numbered as, e.g. "linux" and 44, as
+            /// Host errors are printed. None if there is no way to express it
+            /// on this host.
+            pub(crate) fn from_marked_raw(os: &str, code: i32) -> Option<$enum> {
+                if os.eq_ignore_ascii_case(host_marker()) {
+                    return $enum::from_host_raw(code);
+                }
+                match Os::from_name(os) {
+                    Some(os) if os == $os => $enum::from_raw(code),
+                    Some(os) => translate(code, os, $os).and_then($enum::from_raw),
+                    None => None,
+                }
+            }
+        }
+    };
+}
+
+host_raw!(BsdError, Os::FreeBsd);
+host_raw!(LinuxError, Os::Linux);
+
+impl BsdError {
+    /// Same error in Linux numbering. None if Linux has no such error.
+    pub fn to_linux_raw(&self) -> Option<i32> {
+        match *self {
+            BsdError::Host(code) => match Os::host() {
+                Some(Os::Linux) => Some(code),
+                Some(host) => translate(code, host, Os::Linux),
+                None => None,
+            },
+            _ => Os::Linux.code_of(self.name()),
+        }
+    }
+}
+
+impl LinuxError {
+    /// Same error in FreeBSD terms. None if FreeBSD has no such error.
+    pub fn to_bsd(&self) -> Option<BsdError> {
+        match *self {
+            LinuxError::Host(code) => Os::host()
+                .and_then(|host| translate(code, host, Os::FreeBsd))
+                .and_then(BsdError::from_raw),
+            _ => Os::FreeBsd.code_of(self.name()).and_then(BsdError::from_raw),
+        }
+    }
+}
+
+// Tables below are taken from libc headers of the corresponding OS. Aliases
+// such as EWOULDBLOCK are left out, so every code maps to exactly one name.
+
+const MACOS: &[(&str, i32)] = &[
+    ("EPERM", 1), ("ENOENT", 2), ("ESRCH", 3), ("EINTR", 4), ("EIO", 5), ("ENXIO", 6), ("E2BIG", 7),
+    ("ENOEXEC", 8), ("EBADF", 9), ("ECHILD", 10), ("EDEADLK", 11), ("ENOMEM", 12), ("EACCES", 13),
+    ("EFAULT", 14), ("ENOTBLK", 15), ("EBUSY", 16), ("EEXIST", 17), ("EXDEV", 18), ("ENODEV", 19),
+    ("ENOTDIR", 20), ("EISDIR", 21), ("EINVAL", 22), ("ENFILE", 23), ("EMFILE", 24), ("ENOTTY", 25),
+    ("ETXTBSY", 26), ("EFBIG", 27), ("ENOSPC", 28), ("ESPIPE", 29), ("EROFS", 30), ("EMLINK", 31),
+    ("EPIPE", 32), ("EDOM", 33), ("ERANGE", 34), ("EAGAIN", 35), ("EINPROGRESS", 36),
+    ("EALREADY", 37), ("ENOTSOCK", 38), ("EDESTADDRREQ", 39), ("EMSGSIZE", 40), ("EPROTOTYPE", 41),
+    ("ENOPROTOOPT", 42), ("EPROTONOSUPPORT", 43), ("ESOCKTNOSUPPORT", 44), ("ENOTSUP", 45),
+    ("EPFNOSUPPORT", 46), ("EAFNOSUPPORT", 47), ("EADDRINUSE", 48), ("EADDRNOTAVAIL", 49),
+    ("ENETDOWN", 50), ("ENETUNREACH", 51), ("ENETRESET", 52), ("ECONNABORTED", 53),
+    ("ECONNRESET", 54), ("ENOBUFS", 55), ("EISCONN", 56), ("ENOTCONN", 57), ("ESHUTDOWN", 58),
+    ("ETOOMANYREFS", 59), ("ETIMEDOUT", 60), ("ECONNREFUSED", 61), ("ELOOP", 62),
+    ("ENAMETOOLONG", 63), ("EHOSTDOWN", 64), ("EHOSTUNREACH", 65), ("ENOTEMPTY", 66),
+    ("EPROCLIM", 67), ("EUSERS", 68), ("EDQUOT", 69), ("ESTALE", 70), ("EREMOTE", 71),
+    ("EBADRPC", 72), ("ERPCMISMATCH", 73), ("EPROGUNAVAIL", 74), ("EPROGMISMATCH", 75),
+    ("EPROCUNAVAIL", 76), ("ENOLCK", 77), ("ENOSYS", 78), ("EFTYPE", 79), ("EAUTH", 80),
+    ("ENEEDAUTH", 81), ("EPWROFF", 82), ("EDEVERR", 83), ("EOVERFLOW", 84), ("EBADEXEC", 85),
+    ("EBADARCH", 86), ("ESHLIBVERS", 87), ("EBADMACHO", 88), ("ECANCELED", 89), ("EIDRM", 90),
+    ("ENOMSG", 91), ("EILSEQ", 92), ("ENOATTR", 93), ("EBADMSG", 94), ("EMULTIHOP", 95),
+    ("ENODATA", 96), ("ENOLINK", 97), ("ENOSR", 98), ("ENOSTR", 99), ("EPROTO", 100),
+    ("ETIME", 101), ("EOPNOTSUPP", 102), ("ENOPOLICY", 103), ("ENOTRECOVERABLE", 104),
+    ("EOWNERDEAD", 105), ("EQFULL", 106), ("ENOTCAPABLE", 107),
+];
+
+const ILLUMOS: &[(&str, i32)] = &[
+    ("EPERM", 1), ("ENOENT", 2), ("ESRCH", 3), ("EINTR", 4), ("EIO", 5), ("ENXIO", 6), ("E2BIG", 7),
+    ("ENOEXEC", 8), ("EBADF", 9), ("ECHILD", 10), ("EAGAIN", 11), ("ENOMEM", 12), ("EACCES", 13),
+    ("EFAULT", 14), ("ENOTBLK", 15), ("EBUSY", 16), ("EEXIST", 17), ("EXDEV", 18), ("ENODEV", 19),
+    ("ENOTDIR", 20), ("EISDIR", 21), ("EINVAL", 22), ("ENFILE", 23), ("EMFILE", 24), ("ENOTTY", 25),
+    ("ETXTBSY", 26), ("EFBIG", 27), ("ENOSPC", 28), ("ESPIPE", 29), ("EROFS", 30), ("EMLINK", 31),
+    ("EPIPE", 32), ("EDOM", 33), ("ERANGE", 34), ("ENOMSG", 35), ("EIDRM", 36), ("ECHRNG", 37),
+    ("EL2NSYNC", 38), ("EL3HLT", 39), ("EL3RST", 40), ("ELNRNG", 41), ("EUNATCH", 42),
+    ("ENOCSI", 43), ("EL2HLT", 44), ("EDEADLK", 45), ("ENOLCK", 46), ("ECANCELED", 47),
+    ("ENOTSUP", 48), ("EDQUOT", 49), ("EBADE", 50), ("EBADR", 51), ("EXFULL", 52), ("ENOANO", 53),
+    ("EBADRQC", 54), ("EBADSLT", 55), ("EDEADLOCK", 56), ("EBFONT", 57), ("EOWNERDEAD", 58),
+    ("ENOTRECOVERABLE", 59), ("ENOSTR", 60), ("ENODATA", 61), ("ETIME", 62), ("ENOSR", 63),
+    ("ENONET", 64), ("ENOPKG", 65), ("EREMOTE", 66), ("ENOLINK", 67), ("EADV", 68), ("ESRMNT", 69),
+    ("ECOMM", 70), ("EPROTO", 71), ("ELOCKUNMAPPED", 72), ("ENOTACTIVE", 73), ("EMULTIHOP", 74),
+    ("EADI", 75), ("EBADMSG", 77), ("ENAMETOOLONG", 78), ("EOVERFLOW", 79), ("ENOTUNIQ", 80),
+    ("EBADFD", 81), ("EREMCHG", 82), ("ELIBACC", 83), ("ELIBBAD", 84), ("ELIBSCN", 85),
+    ("ELIBMAX", 86), ("ELIBEXEC", 87), ("EILSEQ", 88), ("ENOSYS", 89), ("ELOOP", 90),
+    ("ERESTART", 91), ("ESTRPIPE", 92), ("ENOTEMPTY", 93), ("EUSERS", 94), ("ENOTSOCK", 95),
+    ("EDESTADDRREQ", 96), ("EMSGSIZE", 97), ("EPROTOTYPE", 98), ("ENOPROTOOPT", 99),
+    ("EPROTONOSUPPORT", 120), ("ESOCKTNOSUPPORT", 121), ("EOPNOTSUPP", 122), ("EPFNOSUPPORT", 123),
+    ("EAFNOSUPPORT", 124), ("EADDRINUSE", 125), ("EADDRNOTAVAIL", 126), ("ENETDOWN", 127),
+    ("ENETUNREACH", 128), ("ENETRESET", 129), ("ECONNABORTED", 130), ("ECONNRESET", 131),
+    ("ENOBUFS", 132), ("EISCONN", 133), ("ENOTCONN", 134), ("ESHUTDOWN", 143),
+    ("ETOOMANYREFS", 144), ("ETIMEDOUT", 145), ("ECONNREFUSED", 146), ("EHOSTDOWN", 147),
+    ("EHOSTUNREACH", 148), ("EALREADY", 149), ("EINPROGRESS", 150), ("ESTALE", 151),
+];
+
+#[test]
+fn translate_tables() {
+    let all = [Os::FreeBsd, Os::Linux, Os::MacOs, Os::Illumos];
+    for &from in &all {
+        for &(name, code) in from.table() {
+            assert_eq!(from.name_of(code), Some(name), "{:?} {}", from, name);
+            for &to in &all {
+                if let Some(translated) = translate(code, from, to) {
+                    assert_eq!(translate(translated, to, from), Some(code));
+                }
+            }
+        }
+    }
+
+    assert_eq!(BsdError::EDEADLK.to_linux_raw(), Some(35));
+    assert_eq!(BsdError::ECAPMODE.to_linux_raw(), None);
+    assert_eq!(BsdError::Unknown(1000).to_linux_raw(), None);
+    assert_eq!(LinuxError::EAGAIN.to_bsd(), Some(BsdError::EAGAIN));
+    assert_eq!(LinuxError::EHWPOISON.to_bsd(), None);
+    assert_eq!(translate(11, Os::Linux, Os::MacOs), Some(35));
+    assert_eq!(translate(122, Os::Illumos, Os::FreeBsd), Some(45)); // EOPNOTSUPP
+    assert_eq!(translate(0, Os::Linux, Os::FreeBsd), None);
+    assert_eq!("freebsd:35".parse(), Ok(LinuxError::EAGAIN));
+    assert_eq!("linux:11".parse(), Ok(BsdError::EAGAIN));
+    assert_eq!("linux:1000".parse(), Ok(LinuxError::Unknown(1000)));
+    assert!("illumos:37".parse::<BsdError>().is_err()); // ECHRNG
+    assert!("plan9:1".parse::<BsdError>().is_err());
+    assert_eq!(BsdError::from_host_raw(0), None);
+    #[cfg(target_os = "linux")]
+    {
+        assert_eq!(BsdError::from_host_raw(11), Some(BsdError::EAGAIN));
+        // ECHRNG, there is no such error on FreeBSD
+        assert_eq!(BsdError::from_host_raw(44), Some(BsdError::Host(44)));
+        assert_eq!(BsdError::Host(44).to_host_raw(), Some(44));
+        assert_eq!(BsdError::Host(44).to_linux_raw(), Some(44));
+        assert_eq!(BsdError::Host(44).raw_os_error(), 44);
+        // 44 is ESOCKTNOSUPPORT in FreeBSD numbering, nothing to do with ECHRNG
+        assert_eq!(BsdError::from_raw(44), Some(BsdError::ESOCKTNOSUPPORT));
+        assert_eq!(BsdError::Unknown(1000).to_host_raw(), None);
+        assert_eq!(LinuxError::from_host_raw(1000), Some(LinuxError::Unknown(1000)));
+        assert_eq!("linux:44".parse(), Ok(BsdError::Host(44)));
+        assert_eq!(BsdError::EAGAIN.to_host_raw(), Some(11));
+        assert_eq!(BsdError::ECAPMODE.to_host_raw(), None);
+        assert_eq!(LinuxError::from_host_raw(11), Some(LinuxError::EAGAIN));
+        assert_eq!(LinuxError::EHWPOISON.to_host_raw(), Some(133));
+    }
+}